@@ -0,0 +1,72 @@
+use std::{fmt::Write, hint::black_box};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use typed_bytesize::{ByteSizeIec, ByteSizeSi, UnitSystem};
+
+const SIZES: [u64; 8] = [
+    0,
+    999,
+    1536,
+    5_368_709_120,
+    1_000_000_000_000,
+    123_456_789_012_345,
+    1 << 60,
+    u64::MAX,
+];
+
+/// Display before the integer-only path, which picked the unit by `f64::log`
+fn display_f64(out: &mut String, n: u64, system: impl UnitSystem) {
+    let (base, symbols) = (system.base(), system.symbols());
+    if n < base {
+        let _ = write!(out, "{n}B");
+        return;
+    }
+
+    let size = n as f64;
+    let exp = (size.log(base as f64) as usize).min(symbols.len());
+    let number = size / (base.pow(exp as u32) as f64);
+    let _ = write!(out, "{number:.1}{}", symbols[exp - 1]);
+}
+
+fn format(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format");
+
+    group.bench_function("f64", |b| {
+        let mut out = String::with_capacity(16);
+        b.iter(|| {
+            for n in SIZES {
+                out.clear();
+                display_f64(&mut out, black_box(n), typed_bytesize::Si);
+                out.clear();
+                display_f64(&mut out, black_box(n), typed_bytesize::Iec);
+            }
+        })
+    });
+
+    group.bench_function("display", |b| {
+        let mut out = String::with_capacity(16);
+        b.iter(|| {
+            for n in SIZES {
+                out.clear();
+                let _ = write!(out, "{}", ByteSizeSi(black_box(n)));
+                out.clear();
+                let _ = write!(out, "{}", ByteSizeIec(black_box(n)));
+            }
+        })
+    });
+
+    group.bench_function("format_to", |b| {
+        let mut buf = [0; 16];
+        b.iter(|| {
+            for n in SIZES {
+                black_box(ByteSizeSi(black_box(n)).format_to(&mut buf).is_ok());
+                black_box(ByteSizeIec(black_box(n)).format_to(&mut buf).is_ok());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, format);
+criterion_main!(benches);
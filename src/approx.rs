@@ -0,0 +1,169 @@
+use crate::UnitSystem;
+
+/// Phrase templates of approximate formatting, where `{}` is replaced by a size like `2 MB`
+///
+/// The default phrases are English, which may be localized:
+///
+/// ```
+/// use typed_bytesize::{ByteSizeSi, Phrases};
+///
+/// let german = Phrases::new()
+///     .less_than("weniger als {}")
+///     .about("etwa {}")
+///     .over("über {}")
+///     .decimal_separator(",");
+/// assert_eq!(ByteSizeSi::mb(1450).approx_with(german).to_string(), "etwa 1,5 GB");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Phrases<'a> {
+    less_than: &'a str,
+    about: &'a str,
+    over: &'a str,
+    exact: &'a str,
+    decimal_separator: &'a str,
+}
+
+impl Default for Phrases<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Phrases<'a> {
+    /// English phrases, like `less than 1 kB`, `about 2 MB` and `over 1 TB`.
+    pub const fn new() -> Self {
+        Self {
+            less_than: "less than {}",
+            about: "about {}",
+            over: "over {}",
+            exact: "{}",
+            decimal_separator: ".",
+        }
+    }
+
+    /// Phrase of sizes below the smallest unit, e.g. `less than {}`.
+    pub const fn less_than(mut self, template: &'a str) -> Self {
+        self.less_than = template;
+        self
+    }
+
+    /// Phrase of sizes close to a whole or half unit, e.g. `about {}`.
+    pub const fn about(mut self, template: &'a str) -> Self {
+        self.about = template;
+        self
+    }
+
+    /// Phrase of sizes clearly beyond a whole or half unit, e.g. `over {}`.
+    pub const fn over(mut self, template: &'a str) -> Self {
+        self.over = template;
+        self
+    }
+
+    /// Phrase of sizes of exactly a whole or half unit, e.g. `{}`.
+    pub const fn exact(mut self, template: &'a str) -> Self {
+        self.exact = template;
+        self
+    }
+
+    /// Separator of the half in `1.5 GB`.
+    pub const fn decimal_separator(mut self, separator: &'a str) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+}
+
+/// Approximate display of bytesize, returned by [`ByteSize::approx`](crate::ByteSize::approx)
+///
+/// Sizes are rounded to half units below 10 units and to whole units beyond,
+/// and are `about` the rounded size if within 5% of it, or `over` the size
+/// rounded down otherwise.
+#[derive(Debug, Clone, Copy)]
+pub struct Approx<'a, S> {
+    pub(crate) bytes: u64,
+    pub(crate) system: S,
+    pub(crate) phrases: Phrases<'a>,
+}
+
+/// Tolerance of `about`, as the reciprocal of the deviation ratio
+const TOLERANCE: u128 = 20;
+
+impl<S: UnitSystem> core::fmt::Display for Approx<'_, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let base = self.system.base();
+        let symbols = self.system.symbols();
+        let phrases = &self.phrases;
+
+        let Some(&first) = symbols.first() else {
+            return fill(f, phrases.exact, Size(self.bytes.into(), 1, "B", ""));
+        };
+        if self.bytes < base {
+            return fill(f, phrases.less_than, Size(1, 1, first, ""));
+        }
+
+        // The largest unit not exceeding the size
+        let mut unit = base;
+        let mut exp = 1;
+        while exp < symbols.len()
+            && let Some(next) = unit.checked_mul(base)
+            && next <= self.bytes
+        {
+            unit = next;
+            exp += 1;
+        }
+
+        let unit = u128::from(unit);
+        let bytes = u128::from(self.bytes);
+        let steps_per_unit = if bytes < 10 * unit { 2 } else { 1 };
+        let floor = bytes * steps_per_unit / unit;
+        let rest = bytes * steps_per_unit % unit;
+        let size = |steps| {
+            Size(
+                steps,
+                steps_per_unit,
+                symbols[exp - 1],
+                phrases.decimal_separator,
+            )
+        };
+
+        // The nearest step, and its distance to the size
+        let (nearest, distance) = match 2 * rest < unit {
+            true => (floor, rest),
+            false => (floor + 1, unit - rest),
+        };
+
+        if rest == 0 {
+            fill(f, phrases.exact, size(floor))
+        } else if TOLERANCE * distance <= unit * nearest {
+            match symbols.get(exp) {
+                // Carried into the next unit, e.g. about 1 MB rather than 1000 kB
+                Some(next) if nearest == u128::from(base) * steps_per_unit => {
+                    fill(f, phrases.about, Size(1, 1, next, ""))
+                }
+                _ => fill(f, phrases.about, size(nearest)),
+            }
+        } else {
+            fill(f, phrases.over, size(floor))
+        }
+    }
+}
+
+/// Size of `steps` steps of the unit `symbol`, where there are 1 or 2 steps per unit
+struct Size<'a>(u128, u128, &'a str, &'a str);
+
+impl core::fmt::Display for Size<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let Self(steps, steps_per_unit, symbol, separator) = *self;
+        match steps % steps_per_unit {
+            0 => write!(f, "{} {symbol}", steps / steps_per_unit),
+            _ => write!(f, "{}{separator}5 {symbol}", steps / steps_per_unit),
+        }
+    }
+}
+
+/// Write `template` with `{}` replaced by `size`.
+fn fill(f: &mut core::fmt::Formatter, template: &str, size: Size) -> core::fmt::Result {
+    match template.split_once("{}") {
+        Some((before, after)) => write!(f, "{before}{size}{after}"),
+        None => f.write_str(template),
+    }
+}
@@ -0,0 +1,168 @@
+//! Single-letter units of base 1024, as in systemd, nginx and JVM flags
+//!
+//! e.g. systemd's `MemoryMax=512M`, nginx's `client_max_body_size 10m`
+//! and JVM's `-Xmx2g`.
+
+use crate::{Error, ErrorKind};
+
+/// Rules of a single-letter base-1024 syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Syntax {
+    /// Letters from `K` on, whose index is the power of 1024
+    letters: &'static str,
+    uppercase: bool,
+    case_sensitive: bool,
+    /// Whether numbers may have a fraction, which is truncated
+    fraction: bool,
+    /// Whether `B` means bytes
+    byte_suffix: bool,
+}
+
+impl Syntax {
+    /// systemd's `parse_size`, e.g. `MemoryMax=1.5G`
+    pub const SYSTEMD: Self = Self {
+        letters: "KMGTPE",
+        uppercase: true,
+        case_sensitive: true,
+        fraction: true,
+        byte_suffix: true,
+    };
+
+    /// nginx's sizes and offsets, e.g. `client_max_body_size 10m`
+    pub const NGINX: Self = Self {
+        letters: "KMG",
+        uppercase: false,
+        case_sensitive: false,
+        fraction: false,
+        byte_suffix: false,
+    };
+
+    /// JVM's memory flags, e.g. `-Xmx2g`
+    pub const JVM: Self = Self {
+        letters: "KMGT",
+        uppercase: false,
+        case_sensitive: false,
+        fraction: false,
+        byte_suffix: false,
+    };
+
+    fn exponent(&self, letter: u8) -> Option<u32> {
+        let letter = if self.case_sensitive {
+            letter
+        } else {
+            letter.to_ascii_uppercase()
+        };
+        self.letters
+            .bytes()
+            .position(|l| l == letter)
+            .map(|i| i as u32 + 1)
+    }
+}
+
+/// Parse bytes in the given syntax.
+pub fn parse(input: &str, syntax: &Syntax) -> Result<u64, Error> {
+    let error = |kind, part| Error::at(kind, input, part);
+
+    if input.is_empty() {
+        return Err(Error::new(ErrorKind::Empty, 0..0, input));
+    }
+
+    let i = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (integer, rest) = input.split_at(i);
+    if integer.is_empty() {
+        let kind = match input.starts_with('-') {
+            true => ErrorKind::Negative,
+            false => ErrorKind::BadNumber,
+        };
+        return Err(error(kind, first_char(input)));
+    }
+
+    let (fraction, suffix) = match rest.strip_prefix('.') {
+        Some(rest) if syntax.fraction => rest.split_at(
+            rest.find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len()),
+        ),
+        _ => ("", rest),
+    };
+    if rest.starts_with('.') && fraction.is_empty() {
+        return Err(error(ErrorKind::BadNumber, &input[..i + 1]));
+    }
+
+    let exponent = match suffix.as_bytes() {
+        [] => 0,
+        [b'B'] if syntax.byte_suffix => 0,
+        [letter] => syntax
+            .exponent(*letter)
+            .ok_or_else(|| error(ErrorKind::UnknownUnit, suffix))?,
+        [letter, ..] => {
+            return match syntax.exponent(*letter) {
+                Some(_) => Err(error(ErrorKind::TrailingInput, &suffix[1..])),
+                None => Err(error(ErrorKind::UnknownUnit, suffix)),
+            };
+        }
+    };
+
+    let overflow = || error(ErrorKind::Overflow, input);
+    let unit = 1024u64.pow(exponent);
+    let integer = integer
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit))
+        .ok_or_else(overflow)?;
+
+    if fraction.is_empty() {
+        return Ok(integer);
+    }
+
+    // Only the leading digits within the precision of u64 matter
+    let fraction = &fraction[..fraction.len().min(19)];
+    let numerator = fraction.parse::<u128>().map_err(|_| overflow())? * unit as u128;
+    let fraction = numerator / 10u128.pow(fraction.len() as u32);
+    integer.checked_add(fraction as u64).ok_or_else(overflow)
+}
+
+fn first_char(s: &str) -> &str {
+    &s[..s.chars().next().map_or(0, char::len_utf8)]
+}
+
+/// Format bytes in the shortest exact form of the given syntax, e.g. `1536m`.
+#[inline]
+pub const fn format(bytes: u64, syntax: &Syntax) -> Formatted {
+    Formatted {
+        bytes,
+        syntax: *syntax,
+    }
+}
+
+/// Bytes formatted by [`format()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formatted {
+    bytes: u64,
+    syntax: Syntax,
+}
+
+impl core::fmt::Display for Formatted {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let letters = self.syntax.letters.as_bytes();
+
+        let mut amount = self.bytes;
+        let mut exponent = 0;
+        while amount != 0 && amount.is_multiple_of(1024) && exponent < letters.len() {
+            amount /= 1024;
+            exponent += 1;
+        }
+
+        write!(f, "{amount}")?;
+        if exponent > 0 {
+            let letter = letters[exponent - 1];
+            let letter = match self.syntax.uppercase {
+                true => letter,
+                false => letter.to_ascii_lowercase(),
+            };
+            write!(f, "{}", letter as char)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+//! GNU coreutils sizes, as in `dd bs=1M`, `head -c 10K` and `truncate -s +2G`
+//!
+//! - `K`, `M`, `G`, ... mean powers of 1024, as `KiB`, `MiB`, `GiB`, ... do,
+//!   where only `K` may also be lowercase;
+//! - `kB`, `MB`, `GB`, ... mean powers of 1000;
+//! - `c`, `w` and `b` mean 1, 2 and 512 bytes;
+//! - `x` multiplies sizes like dd does, e.g. `2x512`.
+
+use core::str::FromStr;
+
+use crate::{Error, ErrorKind};
+
+/// Parse bytes of a size, which may be a product like `2x512`.
+pub fn parse(input: &str) -> Result<u64, Error> {
+    if input.is_empty() {
+        return Err(Error::new(ErrorKind::Empty, 0..0, input));
+    }
+
+    input.split('x').try_fold(1u64, |product, factor| {
+        parse_factor(input, factor)?
+            .checked_mul(product)
+            .ok_or_else(|| Error::at(ErrorKind::Overflow, input, input))
+    })
+}
+
+/// Parse a factor of a product, which must be a substring of `input`.
+fn parse_factor(input: &str, factor: &str) -> Result<u64, Error> {
+    let error = |kind, part| Error::at(kind, input, part);
+
+    let i = factor
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(factor.len());
+    let (number, suffix) = factor.split_at(i);
+    if number.is_empty() {
+        let kind = match factor.starts_with('-') {
+            true => ErrorKind::Negative,
+            false => ErrorKind::BadNumber,
+        };
+        return Err(error(kind, factor));
+    }
+
+    let multiplier = parse_suffix(suffix).ok_or_else(|| {
+        let suggestion = match suffix.as_bytes() {
+            [b'i' | b'I', ..] => None,
+            // Only `k` may be lowercase
+            [b'm'] => Some("M"),
+            [b'g'] => Some("G"),
+            [b't'] => Some("T"),
+            [b'p'] => Some("P"),
+            [b'e'] => Some("E"),
+            _ => crate::parse_unit(suffix).map(|unit| unit.symbol()),
+        };
+        error(ErrorKind::UnknownUnit, suffix).with_suggestion(suggestion)
+    })?;
+
+    let overflow = || error(ErrorKind::Overflow, factor);
+    let number = number.parse::<u64>().map_err(|_| overflow())?;
+    match multiplier {
+        Some(multiplier) => number.checked_mul(multiplier).ok_or_else(overflow),
+        // Beyond exabytes, only zero is representable
+        None if number == 0 => Ok(0),
+        None => Err(overflow()),
+    }
+}
+
+/// Multiplier of a suffix, which is `None` if beyond `u64`.
+fn parse_suffix(suffix: &str) -> Option<Option<u64>> {
+    let (letter, base) = match suffix.as_bytes() {
+        [] | [b'c'] => return Some(Some(1)),
+        [b'w'] => return Some(Some(2)),
+        [b'b'] => return Some(Some(512)),
+        [letter] | [letter, b'i', b'B'] => (*letter, 1024u64),
+        [letter, b'B'] => (*letter, 1000),
+        _ => return None,
+    };
+
+    let exponent = match letter {
+        b'k' | b'K' => 1,
+        b'M' => 2,
+        b'G' => 3,
+        b'T' => 4,
+        b'P' => 5,
+        b'E' => 6,
+        b'Z' => 7,
+        b'Y' => 8,
+        b'R' => 9,
+        b'Q' => 10,
+        _ => return None,
+    };
+
+    Some(base.checked_pow(exponent))
+}
+
+/// Format bytes in the shortest exact form, e.g. `4M`, `1kB` or `1000`.
+#[inline]
+pub const fn format(bytes: u64) -> Formatted {
+    Formatted(bytes)
+}
+
+/// Bytes formatted by [`format()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formatted(u64);
+
+impl core::fmt::Display for Formatted {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        const LETTERS: &[u8] = b"KMGTPE";
+
+        let scaled = |base: u64| {
+            let mut amount = self.0;
+            let mut exponent = 0;
+            while amount != 0 && amount.is_multiple_of(base) && exponent < LETTERS.len() {
+                amount /= base;
+                exponent += 1;
+            }
+            (amount, exponent)
+        };
+
+        let (binary, binary_exp) = scaled(1024);
+        let (decimal, decimal_exp) = scaled(1000);
+
+        // `kB` is one letter longer than `K`
+        if decimal_exp == 0 || (binary_exp > 0 && digits(binary) <= digits(decimal) + 1) {
+            write!(f, "{binary}")?;
+            if binary_exp > 0 {
+                write!(f, "{}", LETTERS[binary_exp - 1] as char)?;
+            }
+        } else {
+            let letter = match LETTERS[decimal_exp - 1] {
+                b'K' => 'k',
+                letter => letter as char,
+            };
+            write!(f, "{decimal}{letter}B")?;
+        }
+        Ok(())
+    }
+}
+
+fn digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// Size modification of `truncate -s`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// `SIZE`
+    Set(u64),
+    /// `+SIZE`
+    Extend(u64),
+    /// `-SIZE`
+    Reduce(u64),
+    /// `<SIZE`
+    AtMost(u64),
+    /// `>SIZE`
+    AtLeast(u64),
+    /// `/SIZE`
+    RoundDown(u64),
+    /// `%SIZE`
+    RoundUp(u64),
+}
+
+impl Operation {
+    /// Apply the operation to `size`, returning `None` on overflow or
+    /// rounding to a multiple of zero.
+    ///
+    /// Reducing saturates at zero as truncate does.
+    pub fn apply(self, size: u64) -> Option<u64> {
+        match self {
+            Self::Set(n) => Some(n),
+            Self::Extend(n) => size.checked_add(n),
+            Self::Reduce(n) => Some(size.saturating_sub(n)),
+            Self::AtMost(n) => Some(size.min(n)),
+            Self::AtLeast(n) => Some(size.max(n)),
+            Self::RoundDown(n) => Some(size.checked_div(n)? * n),
+            Self::RoundUp(n) => size.checked_next_multiple_of(n),
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (operation, size): (fn(u64) -> Self, _) = match s.as_bytes().first() {
+            Some(b'+') => (Self::Extend, &s[1..]),
+            Some(b'-') => (Self::Reduce, &s[1..]),
+            Some(b'<') => (Self::AtMost, &s[1..]),
+            Some(b'>') => (Self::AtLeast, &s[1..]),
+            Some(b'/') => (Self::RoundDown, &s[1..]),
+            Some(b'%') => (Self::RoundUp, &s[1..]),
+            _ => (Self::Set, s),
+        };
+
+        if size.is_empty() {
+            return Err(Error::new(ErrorKind::Empty, s.len()..s.len(), s));
+        }
+
+        let n = parse(size).map_err(|e| e.within(s, size))?;
+        let operation = operation(n);
+        if n == 0 && matches!(operation, Self::RoundDown(_) | Self::RoundUp(_)) {
+            return Err(Error::at(ErrorKind::BadNumber, s, size));
+        }
+        Ok(operation)
+    }
+}
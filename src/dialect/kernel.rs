@@ -0,0 +1,111 @@
+//! Linux kernel text in procfs and sysfs, where `kB` means KiB
+//!
+//! e.g. `MemTotal: 16318480 kB` in `/proc/meminfo` or `VmRSS: 1234 kB` in
+//! `/proc/<pid>/status`, while bare numbers in sysfs like `memory.max` are bytes.
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+
+use crate::{ByteSizeIec, Error, ErrorKind, KIB};
+
+/// Parse bytes of a value like `1234 kB` or `4096`.
+pub fn parse(input: &str) -> Result<u64, Error> {
+    let error = |kind, part| Error::at(kind, input, part);
+
+    let value = input.trim_matches(|c: char| c.is_ascii_whitespace());
+    if value.is_empty() {
+        return Err(Error::new(ErrorKind::Empty, 0..input.len(), input));
+    }
+
+    let i = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, rest) = value.split_at(i);
+    if number.is_empty() {
+        let kind = match value.starts_with('-') {
+            true => ErrorKind::Negative,
+            false => ErrorKind::BadNumber,
+        };
+        return Err(error(kind, value));
+    }
+
+    let unit = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let multiplier = match unit {
+        "" => 1,
+        "kB" => KIB,
+        _ => {
+            let suggestion = unit.eq_ignore_ascii_case("kB").then_some("kB");
+            return Err(error(ErrorKind::UnknownUnit, unit).with_suggestion(suggestion));
+        }
+    };
+
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| error(ErrorKind::Overflow, number))
+}
+
+/// Format bytes as the kernel does, e.g. `1234 kB`, or bytes if not whole KiB.
+#[inline]
+pub const fn format(bytes: u64) -> Formatted {
+    Formatted(bytes)
+}
+
+/// Bytes formatted by [`format()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formatted(u64);
+
+impl core::fmt::Display for Formatted {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.0.is_multiple_of(KIB) {
+            true => write!(f, "{} kB", self.0 / KIB),
+            false => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// Iterate over `key: value kB` entries of a file like `/proc/meminfo`.
+///
+/// Lines whose values aren't in `kB`, like `HugePages_Total: 0` or `Name: bash`,
+/// are skipped. Keys may repeat, as in `/proc/<pid>/smaps`.
+///
+/// Errors are located in the offending line.
+#[inline]
+pub fn entries(text: &str) -> Entries<'_> {
+    Entries {
+        lines: text.lines(),
+    }
+}
+
+/// Iterator returned by [`entries`]
+#[derive(Debug, Clone)]
+pub struct Entries<'a> {
+    lines: core::str::Lines<'a>,
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Result<(&'a str, ByteSizeIec), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if !value.trim_end().ends_with("kB") {
+                return None;
+            }
+            let entry = parse(value)
+                .map(|bytes| (key.trim(), ByteSizeIec(bytes)))
+                .map_err(|e| e.within(line, value));
+            Some(entry)
+        })
+    }
+}
+
+/// Parse `key: value kB` entries of a file like `/proc/meminfo` into a map.
+///
+/// Later entries of a repeated key replace earlier ones, see [`entries`] to
+/// handle them otherwise.
+#[cfg(feature = "alloc")]
+pub fn parse_file(text: &str) -> Result<BTreeMap<&str, ByteSizeIec>, Error> {
+    entries(text).collect()
+}
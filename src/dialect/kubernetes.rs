@@ -0,0 +1,229 @@
+//! Kubernetes `resource.Quantity`, following apimachinery
+//!
+//! ```ignore
+//! quantity        ::= signedNumber suffix
+//! signedNumber    ::= number | sign number
+//! sign            ::= "+" | "-"
+//! number          ::= digits | digits "." digits | digits "." | "." digits
+//! suffix          ::= binarySI | decimalExponent | decimalSI
+//! binarySI        ::= "Ki" | "Mi" | "Gi" | "Ti" | "Pi" | "Ei"
+//! decimalSI       ::= "m" | "" | "k" | "M" | "G" | "T" | "P" | "E"
+//! decimalExponent ::= ("e" | "E") signedNumber
+//! ```
+//!
+//! Since bytes are integers, fractional quantities like `1500m` are rounded up
+//! as `Quantity.Value()` does.
+
+use core::str::FromStr;
+
+use crate::{Error, ErrorKind, Unit, parse_unit};
+
+/// Suffix style of a quantity, which is kept when canonicalizing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// e.g. `128Mi`
+    BinarySI,
+    /// e.g. `1G`
+    DecimalSI,
+    /// e.g. `1e9`
+    DecimalExponent,
+}
+
+/// Bytes with the suffix style of a quantity
+///
+/// It's displayed in the canonical form of apimachinery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quantity {
+    bytes: u64,
+    format: Format,
+}
+
+impl Quantity {
+    #[inline]
+    pub const fn new(bytes: u64, format: Format) -> Self {
+        Self { bytes, format }
+    }
+
+    #[inline]
+    pub const fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    #[inline]
+    pub const fn format(&self) -> Format {
+        self.format
+    }
+}
+
+/// Parse bytes of a quantity.
+pub fn parse(input: &str) -> Result<u64, Error> {
+    Quantity::from_str(input).map(|q| q.bytes)
+}
+
+impl FromStr for Quantity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |kind, part| Error::at(kind, s, part);
+
+        if s.is_empty() {
+            return Err(Error::new(ErrorKind::Empty, 0..0, s));
+        }
+
+        let (negative, unsigned) = match s.as_bytes()[0] {
+            b'-' => (true, &s[1..]),
+            b'+' => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let integer_len = unsigned
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(unsigned.len());
+        let (integer, rest) = unsigned.split_at(integer_len);
+        let (fraction, suffix) = match rest.strip_prefix('.') {
+            Some(rest) => rest.split_at(
+                rest.find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len()),
+            ),
+            None => ("", rest),
+        };
+        let number = &unsigned[..unsigned.len() - suffix.len()];
+
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(error(ErrorKind::BadNumber, number));
+        }
+
+        let (exp2, exp10, format) = parse_suffix(suffix).ok_or_else(|| {
+            let suggestion = parse_unit(suffix).map(suffix_of);
+            error(ErrorKind::UnknownUnit, suffix).with_suggestion(suggestion)
+        })?;
+
+        let mut mantissa: u128 = 0;
+        for b in integer.bytes().chain(fraction.bytes()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add((b - b'0') as u128))
+                .ok_or_else(|| error(ErrorKind::Overflow, s))?;
+        }
+
+        if mantissa == 0 {
+            return Ok(Self::new(0, format));
+        }
+        if negative {
+            return Err(error(ErrorKind::Negative, &s[..1]));
+        }
+
+        let bytes = scale(mantissa, exp2, exp10 - fraction.len() as i64)
+            .ok_or_else(|| error(ErrorKind::Overflow, s))?;
+        Ok(Self::new(bytes, format))
+    }
+}
+
+/// Binary exponent, decimal exponent and format of a suffix
+fn parse_suffix(suffix: &str) -> Option<(u32, i64, Format)> {
+    let (exp2, exp10, format) = match suffix {
+        "Ki" => (10, 0, Format::BinarySI),
+        "Mi" => (20, 0, Format::BinarySI),
+        "Gi" => (30, 0, Format::BinarySI),
+        "Ti" => (40, 0, Format::BinarySI),
+        "Pi" => (50, 0, Format::BinarySI),
+        "Ei" => (60, 0, Format::BinarySI),
+        "m" => (0, -3, Format::DecimalSI),
+        "" => (0, 0, Format::DecimalSI),
+        "k" => (0, 3, Format::DecimalSI),
+        "M" => (0, 6, Format::DecimalSI),
+        "G" => (0, 9, Format::DecimalSI),
+        "T" => (0, 12, Format::DecimalSI),
+        "P" => (0, 15, Format::DecimalSI),
+        "E" => (0, 18, Format::DecimalSI),
+        _ => {
+            let exponent = suffix.strip_prefix(['e', 'E'])?;
+            let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            (0, exponent.parse().ok()?, Format::DecimalExponent)
+        }
+    };
+    Some((exp2, exp10, format))
+}
+
+/// Suffix of a unit in quantities
+fn suffix_of(unit: Unit) -> &'static str {
+    match unit {
+        Unit::B => "",
+        Unit::KB => "k",
+        Unit::MB => "M",
+        Unit::GB => "G",
+        Unit::TB => "T",
+        Unit::PB => "P",
+        Unit::EB => "E",
+        Unit::KiB => "Ki",
+        Unit::MiB => "Mi",
+        Unit::GiB => "Gi",
+        Unit::TiB => "Ti",
+        Unit::PiB => "Pi",
+        Unit::EiB => "Ei",
+    }
+}
+
+/// `mantissa * 2^exp2 * 10^exp10` rounded up, or `None` on overflow.
+fn scale(mantissa: u128, exp2: u32, exp10: i64) -> Option<u64> {
+    let value = mantissa.checked_mul(1 << exp2)?;
+
+    let value = if exp10 >= 0 {
+        10u128
+            .checked_pow(exp10.try_into().ok()?)
+            .and_then(|n| value.checked_mul(n))?
+    } else {
+        match 10u128.checked_pow(exp10.unsigned_abs().try_into().ok()?) {
+            Some(n) => value.div_ceil(n),
+            // Any positive value is less than such a divisor
+            None => 1,
+        }
+    };
+
+    value.try_into().ok()
+}
+
+impl core::fmt::Display for Quantity {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if self.bytes == 0 {
+            return f.write_str("0");
+        }
+
+        // Small values are shown as decimal to avoid confusion
+        if self.format == Format::BinarySI && self.bytes >= 1024 {
+            let (amount, exponent) = remove_factors(self.bytes, 1024);
+            let suffix = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"][exponent as usize];
+            return write!(f, "{amount}{suffix}");
+        }
+
+        // Keep the exponent a multiple of 3
+        let (amount, exponent) = match remove_factors(self.bytes, 10) {
+            (amount, exponent) if exponent % 3 == 1 => (amount * 10, exponent - 1),
+            (amount, exponent) if exponent % 3 == 2 => (amount * 100, exponent - 2),
+            canonical => canonical,
+        };
+
+        if self.format == Format::DecimalExponent {
+            match exponent {
+                0 => write!(f, "{amount}"),
+                _ => write!(f, "{amount}e{exponent}"),
+            }
+        } else {
+            let suffix = ["", "k", "M", "G", "T", "P", "E"][exponent as usize / 3];
+            write!(f, "{amount}{suffix}")
+        }
+    }
+}
+
+/// Divide out `factor` from `n`, returning the rest and the times divided.
+fn remove_factors(mut n: u64, factor: u64) -> (u64, u32) {
+    let mut times = 0;
+    while n != 0 && n.is_multiple_of(factor) {
+        n /= factor;
+        times += 1;
+    }
+    (n, times)
+}
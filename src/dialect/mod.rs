@@ -0,0 +1,78 @@
+//! Size syntaxes of other tools and formats
+
+pub mod base1024;
+pub mod coreutils;
+pub mod kernel;
+pub mod kubernetes;
+pub mod postgres;
+pub mod redis;
+
+use crate::Error;
+
+/// Size syntax of a tool or format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    /// Kubernetes `resource.Quantity`, e.g. `128Mi`, `1G` or `1e9`
+    Kubernetes,
+    /// systemd's single-letter units of base 1024, e.g. `MemoryMax=512M`
+    Systemd,
+    /// nginx's single-letter units of base 1024, e.g. `10m`
+    Nginx,
+    /// JVM's single-letter units of base 1024, e.g. `-Xmx2g`
+    Jvm,
+    /// GNU coreutils sizes, e.g. `dd bs=1M` or `head -c 10kB`
+    Coreutils,
+    /// Redis memory sizes, e.g. `maxmemory 2gb`, where `1k` is 1000 but `1kb` is 1024
+    Redis,
+    /// PostgreSQL memory parameters of base 1024, e.g. `shared_buffers = 128MB`
+    Postgres,
+    /// Linux kernel text in procfs and sysfs, e.g. `VmRSS: 1234 kB`, where `kB` means KiB
+    Kernel,
+}
+
+impl Dialect {
+    /// Parse bytes in the syntax of the dialect.
+    pub fn parse(self, input: &str) -> Result<u64, Error> {
+        match self {
+            Self::Kubernetes => kubernetes::parse(input),
+            Self::Systemd => base1024::parse(input, &base1024::Syntax::SYSTEMD),
+            Self::Nginx => base1024::parse(input, &base1024::Syntax::NGINX),
+            Self::Jvm => base1024::parse(input, &base1024::Syntax::JVM),
+            Self::Coreutils => coreutils::parse(input),
+            Self::Redis => redis::parse(input),
+            Self::Postgres => postgres::parse(input),
+            Self::Kernel => kernel::parse(input),
+        }
+    }
+}
+
+/// Bytesize formatted in the syntax of a [`Dialect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DialectDisplay {
+    pub(crate) dialect: Dialect,
+    pub(crate) bytes: u64,
+    /// Whether binary prefixes are preferred
+    pub(crate) binary: bool,
+}
+
+impl core::fmt::Display for DialectDisplay {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.dialect {
+            Dialect::Kubernetes => {
+                let format = if self.binary {
+                    kubernetes::Format::BinarySI
+                } else {
+                    kubernetes::Format::DecimalSI
+                };
+                kubernetes::Quantity::new(self.bytes, format).fmt(f)
+            }
+            Dialect::Systemd => base1024::format(self.bytes, &base1024::Syntax::SYSTEMD).fmt(f),
+            Dialect::Nginx => base1024::format(self.bytes, &base1024::Syntax::NGINX).fmt(f),
+            Dialect::Jvm => base1024::format(self.bytes, &base1024::Syntax::JVM).fmt(f),
+            Dialect::Coreutils => coreutils::format(self.bytes).fmt(f),
+            Dialect::Redis => redis::format(self.bytes).fmt(f),
+            Dialect::Postgres => postgres::format(self.bytes).fmt(f),
+            Dialect::Kernel => kernel::format(self.bytes).fmt(f),
+        }
+    }
+}
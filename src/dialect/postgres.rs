@@ -0,0 +1,147 @@
+//! PostgreSQL memory parameters in `postgresql.conf`, following `parse_int`
+//!
+//! Units are case-sensitive `B`, `kB`, `MB`, `GB` and `TB` of base 1024.
+//! As PostgreSQL does, integers may be hexadecimal like `0x10` or octal like `010`,
+//! and fractional numbers are rounded to the nearest base unit.
+
+use core::num::{IntErrorKind, ParseIntError};
+
+use crate::{Error, ErrorKind, GIB, KIB, MIB, TIB, f64_to_u64};
+
+const UNITS: [(&str, u64); 5] = [("TB", TIB), ("GB", GIB), ("MB", MIB), ("kB", KIB), ("B", 1)];
+
+/// Parse bytes of a memory parameter, whose bare numbers are bytes.
+#[inline]
+pub fn parse(input: &str) -> Result<u64, Error> {
+    parse_with_base_unit(input, 1)
+}
+
+/// Parse bytes of a memory parameter, whose bare numbers are in `base_unit` bytes,
+/// e.g. 8192 for `shared_buffers`.
+pub fn parse_with_base_unit(input: &str, base_unit: u64) -> Result<u64, Error> {
+    let error = |kind, part| Error::at(kind, input, part);
+
+    let trimmed = input.trim_matches(|c: char| c.is_ascii_whitespace());
+    if trimmed.is_empty() {
+        return Err(Error::new(ErrorKind::Empty, 0..input.len(), input));
+    }
+    if trimmed.starts_with('-') {
+        return Err(error(ErrorKind::Negative, &trimmed[..1]));
+    }
+
+    let (number, rest) = trimmed.split_at(number_len(trimmed));
+    if number.is_empty() || number == "+" {
+        return Err(error(ErrorKind::BadNumber, trimmed));
+    }
+
+    let unit = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let multiplier = match unit {
+        "" => base_unit,
+        _ => UNITS
+            .into_iter()
+            .find_map(|(symbol, multiplier)| (unit == symbol).then_some(multiplier))
+            .ok_or_else(|| {
+                let suggestion = UNITS
+                    .into_iter()
+                    .find(|(symbol, _)| unit.eq_ignore_ascii_case(symbol))
+                    .map(|(symbol, _)| symbol);
+                error(ErrorKind::UnknownUnit, unit).with_suggestion(suggestion)
+            })?,
+    };
+
+    let overflow = || error(ErrorKind::Overflow, number);
+    let unsigned = number.strip_prefix('+').unwrap_or(number);
+
+    if let Some(integer) = parse_integer(unsigned) {
+        return match integer {
+            Ok(n) => n.checked_mul(multiplier).ok_or_else(overflow),
+            Err(e) if *e.kind() == IntErrorKind::PosOverflow => Err(overflow()),
+            Err(_) => Err(error(ErrorKind::BadNumber, number)),
+        };
+    }
+
+    let float = unsigned
+        .parse::<f64>()
+        .map_err(|_| error(ErrorKind::BadNumber, number))?
+        * multiplier as f64;
+    round_ties_even(float).ok_or_else(overflow)
+}
+
+/// Length of the leading number, in the forms of `strtol` or `strtod`
+fn number_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut i = usize::from(bytes[0] == b'+');
+    let count = |i: usize, f: fn(&u8) -> bool| bytes[i..].iter().take_while(|b| f(b)).count();
+    let digits = |i: usize| count(i, u8::is_ascii_digit);
+
+    if bytes[i..].starts_with(b"0x") || bytes[i..].starts_with(b"0X") {
+        return i + 2 + count(i + 2, u8::is_ascii_hexdigit);
+    }
+
+    i += digits(i);
+    if bytes.get(i) == Some(&b'.') {
+        i += 1 + digits(i + 1);
+    }
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(i + 1), Some(b'+' | b'-')));
+        let exponent = digits(i + 1 + sign);
+        if exponent > 0 {
+            i += 1 + sign + exponent;
+        }
+    }
+    i
+}
+
+/// Parse an integer as `strtol` of base 0, or `None` if it's a float.
+fn parse_integer(number: &str) -> Option<Result<u64, ParseIntError>> {
+    if let Some(hex) = number
+        .strip_prefix("0x")
+        .or_else(|| number.strip_prefix("0X"))
+    {
+        return Some(u64::from_str_radix(hex, 16));
+    }
+    if !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    match number.strip_prefix('0') {
+        Some(octal) if !octal.is_empty() => Some(u64::from_str_radix(octal, 8)),
+        _ => Some(number.parse()),
+    }
+}
+
+/// `rint` of a number, or `None` if it's out of `u64`
+fn round_ties_even(x: f64) -> Option<u64> {
+    let integer = f64_to_u64(x)?;
+    let fraction = x - integer as f64;
+    if fraction > 0.5 || (fraction == 0.5 && integer % 2 == 1) {
+        Some(integer + 1)
+    } else {
+        Some(integer)
+    }
+}
+
+/// Format bytes in the largest exact unit as `SHOW` does, e.g. `1536MB` or `8kB`.
+#[inline]
+pub const fn format(bytes: u64) -> Formatted {
+    Formatted(bytes)
+}
+
+/// Bytes formatted by [`format()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formatted(u64);
+
+impl core::fmt::Display for Formatted {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let bytes = self.0;
+        if bytes == 0 {
+            return write!(f, "0");
+        }
+
+        // `B` divides anything
+        let (symbol, multiplier) = UNITS
+            .into_iter()
+            .find(|(_, multiplier)| bytes.is_multiple_of(*multiplier))
+            .unwrap_or(("B", 1));
+        write!(f, "{}{symbol}", bytes / multiplier)
+    }
+}
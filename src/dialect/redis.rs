@@ -0,0 +1,75 @@
+//! Redis memory sizes in `redis.conf`, following `memtoll`
+//!
+//! Units are case-insensitive, where `k`, `m` and `g` mean powers of 1000,
+//! while `kb`, `mb` and `gb` mean powers of 1024.
+
+use crate::{Error, ErrorKind, GB, GIB, KB, KIB, MB, MIB};
+
+/// Parse bytes of a memory size.
+pub fn parse(input: &str) -> Result<u64, Error> {
+    let error = |kind, part| Error::at(kind, input, part);
+
+    if input.is_empty() {
+        return Err(Error::new(ErrorKind::Empty, 0..0, input));
+    }
+
+    let i = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(i);
+
+    if number.is_empty() {
+        let kind = match input.starts_with('-') {
+            true => ErrorKind::Negative,
+            false => ErrorKind::BadNumber,
+        };
+        return Err(error(kind, input));
+    }
+
+    let multiplier = [
+        ("", 1),
+        ("b", 1),
+        ("k", KB),
+        ("kb", KIB),
+        ("m", MB),
+        ("mb", MIB),
+        ("g", GB),
+        ("gb", GIB),
+    ]
+    .into_iter()
+    .find_map(|(symbol, multiplier)| unit.eq_ignore_ascii_case(symbol).then_some(multiplier))
+    .ok_or_else(|| error(ErrorKind::UnknownUnit, unit))?;
+
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| error(ErrorKind::Overflow, input))
+}
+
+/// Format bytes as `CONFIG REWRITE` does, e.g. `2gb`, `1536mb` or `1000`.
+#[inline]
+pub const fn format(bytes: u64) -> Formatted {
+    Formatted(bytes)
+}
+
+/// Bytes formatted by [`format()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formatted(u64);
+
+impl core::fmt::Display for Formatted {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let bytes = self.0;
+        if bytes == 0 {
+            write!(f, "0")
+        } else if bytes.is_multiple_of(GIB) {
+            write!(f, "{}gb", bytes / GIB)
+        } else if bytes.is_multiple_of(MIB) {
+            write!(f, "{}mb", bytes / MIB)
+        } else if bytes.is_multiple_of(KIB) {
+            write!(f, "{}kb", bytes / KIB)
+        } else {
+            write!(f, "{bytes}")
+        }
+    }
+}
@@ -0,0 +1,229 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::ops::Range;
+
+/// Error of bytesize parsing
+///
+/// It records which part of the input is wrong, and with feature `alloc`, its
+/// [`Display`](core::fmt::Display) renders a caret diagram pointing at the part:
+///
+/// ```text
+/// cannot recognize byte unit in string
+///     512 MiBs
+///         ^^^^ did you mean 'MiB'?
+/// ```
+///
+/// Without `alloc`, the input isn't kept and only the span is displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    span: Range<usize>,
+    #[cfg(feature = "alloc")]
+    input: Box<str>,
+    suggestion: Option<&'static str>,
+}
+
+/// Kind of [`Error`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Empty,
+    BadNumber,
+    UnknownUnit,
+    AmbiguousUnit,
+    Overflow,
+    Negative,
+    TrailingInput,
+    FractionalBytes,
+    /// Malformed input which isn't covered by other kinds
+    Invalid,
+}
+
+/// Error of converting bytesize from or into other numeric types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConversionError {
+    kind: ErrorKind,
+}
+
+impl ConversionError {
+    pub(crate) const fn new(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// One of [`ErrorKind::Negative`], [`ErrorKind::Overflow`],
+    /// [`ErrorKind::FractionalBytes`] and [`ErrorKind::BadNumber`] for NaN
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl core::error::Error for ConversionError {}
+
+impl core::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.kind.message())
+    }
+}
+
+impl Error {
+    pub(crate) fn new(kind: ErrorKind, span: Range<usize>, input: &str) -> Self {
+        #[cfg(not(feature = "alloc"))]
+        let _ = input;
+        Self {
+            kind,
+            span,
+            #[cfg(feature = "alloc")]
+            input: input.into(),
+            suggestion: None,
+        }
+    }
+
+    /// Error of `part`, which must be a substring of `input`.
+    pub(crate) fn at(kind: ErrorKind, input: &str, part: &str) -> Self {
+        let start = part.as_ptr() as usize - input.as_ptr() as usize;
+        Self::new(kind, start..start + part.len(), input)
+    }
+
+    pub(crate) fn with_suggestion(mut self, suggestion: Option<&'static str>) -> Self {
+        self.suggestion = suggestion;
+        self
+    }
+
+    /// Relocate the error of `part`, which must be a substring of `input`.
+    pub(crate) fn within(mut self, input: &str, part: &str) -> Self {
+        let offset = part.as_ptr() as usize - input.as_ptr() as usize;
+        self.span = self.span.start + offset..self.span.end + offset;
+        #[cfg(feature = "alloc")]
+        {
+            self.input = input.into();
+        }
+        self
+    }
+
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Byte range of the offending part in the input
+    #[inline]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The whole input being parsed
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The known unit symbol closest to an unrecognized one
+    #[inline]
+    pub fn suggestion(&self) -> Option<&'static str> {
+        self.suggestion
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl core::fmt::Display for Error {
+    #[cfg(feature = "alloc")]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if self.input.is_empty() {
+            return Ok(());
+        }
+
+        let pad = self.input[..self.span.start].chars().count();
+        let carets = self.input[self.span.clone()].chars().count().max(1);
+        write!(f, "\n    {}\n    {:pad$}{:^<carets$}", self.input, "", "")?;
+
+        if let Some(suggestion) = self.suggestion {
+            write!(f, " did you mean '{suggestion}'?")?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)?;
+
+        if let Some(suggestion) = self.suggestion {
+            write!(f, " did you mean '{suggestion}'?")?;
+        }
+        Ok(())
+    }
+}
+
+impl ErrorKind {
+    /// Description of the kind, also usable in const panics
+    pub(crate) const fn message(self) -> &'static str {
+        match self {
+            Self::Empty => "cannot parse bytesize from empty string",
+            Self::BadNumber => "invalid number found in string",
+            Self::UnknownUnit => "cannot recognize byte unit in string",
+            Self::AmbiguousUnit => "lowercase 'b' in unit may mean bits rather than bytes",
+            Self::Overflow => "bytesize is too large to represent",
+            Self::Negative => "bytesize cannot be negative",
+            Self::TrailingInput => "unexpected input after byte unit",
+            Self::FractionalBytes => "bytesize cannot have fractional bytes",
+            Self::Invalid => "invalid bytesize expression",
+        }
+    }
+}
+
+impl core::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+/// Longest unit symbol that may be suggested
+const MAX_SYMBOL_LEN: usize = 7;
+
+/// Find the unit symbol among `candidates` closest to `token` by case-insensitive edit distance.
+pub(crate) fn suggest_unit(
+    token: &str,
+    candidates: impl IntoIterator<Item = &'static str>,
+) -> Option<&'static str> {
+    candidates
+        .into_iter()
+        .filter(|symbol| !symbol.is_empty() && symbol.len() <= MAX_SYMBOL_LEN)
+        .map(|symbol| {
+            let distance = edit_distance(token.as_bytes(), symbol.as_bytes());
+            // Among equally distant symbols, prefer the one of the same prefix
+            let other_prefix = !token
+                .get(..1)
+                .zip(symbol.get(..1))
+                .is_some_and(|(a, b)| a.eq_ignore_ascii_case(b));
+            (distance, other_prefix, symbol)
+        })
+        .filter(|(distance, ..)| *distance <= 2 && *distance < token.len())
+        .min_by_key(|(distance, other_prefix, _)| (*distance, *other_prefix))
+        .map(|(.., symbol)| symbol)
+}
+
+/// Levenshtein distance ignoring ASCII case, where `b` is as short as a unit symbol.
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    const MAX: usize = MAX_SYMBOL_LEN + 1;
+    debug_assert!(b.len() < MAX);
+
+    let mut prev = [0; MAX];
+    let mut curr = [0; MAX];
+    for (j, d) in prev.iter_mut().enumerate().take(b.len() + 1) {
+        *d = j;
+    }
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(!ca.eq_ignore_ascii_case(cb));
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+
+    prev[b.len()]
+}
@@ -0,0 +1,102 @@
+use crate::{ByteSizeIec, ByteSizeSi, Unit, f64_to_u64};
+
+macro_rules! ext_methods {
+    ($($ty:ident { $($name:ident, $checked:ident => $unit:ident;)+ })+) => {
+        $($(
+            #[doc = concat!("`", stringify!($unit), "`s as [`", stringify!($ty), "`](type@", stringify!($ty), ")")]
+            #[inline]
+            #[track_caller]
+            fn $name(self) -> $ty {
+                match self.$checked() {
+                    Some(size) => size,
+                    None => panic!("bytesize is negative or too large to represent"),
+                }
+            }
+
+            #[doc = concat!("`", stringify!($unit), "`s as [`", stringify!($ty), "`](type@", stringify!($ty), "), or `None` if negative or overflowing")]
+            #[inline]
+            fn $checked(self) -> Option<$ty> {
+                self.checked_bytes(Unit::$unit).map($ty)
+            }
+        )+)+
+    };
+}
+
+/// Literal-style constructors of bytesize on primitive numbers
+///
+/// ```
+/// use typed_bytesize::{ByteSizeExt, ByteSizeIec, ByteSizeSi};
+///
+/// assert_eq!(64.mib(), ByteSizeIec::mib(64));
+/// assert_eq!(2.gb(), ByteSizeSi::gb(2));
+/// assert_eq!(1.5.gib(), ByteSizeIec::mib(1536));
+/// assert_eq!((-1).checked_kib(), None);
+/// assert_eq!(u64::MAX.checked_kb(), None);
+/// ```
+///
+/// Fractional bytes are rounded to the nearest. The unchecked constructors
+/// panic on negative or overflowing sizes.
+pub trait ByteSizeExt: Copy {
+    /// Bytes of `self` in `unit`, or `None` if negative, overflowing or not a number
+    fn checked_bytes(self, unit: Unit) -> Option<u64>;
+
+    ext_methods! {
+        ByteSizeSi {
+            kb, checked_kb => KB;
+            mb, checked_mb => MB;
+            gb, checked_gb => GB;
+            tb, checked_tb => TB;
+            pb, checked_pb => PB;
+            eb, checked_eb => EB;
+        }
+        ByteSizeIec {
+            kib, checked_kib => KiB;
+            mib, checked_mib => MiB;
+            gib, checked_gib => GiB;
+            tib, checked_tib => TiB;
+            pib, checked_pib => PiB;
+            eib, checked_eib => EiB;
+        }
+    }
+}
+
+macro_rules! impl_integer {
+    ($($ty:ty),+) => {
+        $(
+            impl ByteSizeExt for $ty {
+                #[inline]
+                fn checked_bytes(self, unit: Unit) -> Option<u64> {
+                    u64::try_from(self).ok()?.checked_mul(unit.bytes())
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+macro_rules! impl_float {
+    ($($ty:ty),+) => {
+        $(
+            impl ByteSizeExt for $ty {
+                #[inline]
+                fn checked_bytes(self, unit: Unit) -> Option<u64> {
+                    round(self as f64 * unit.bytes() as f64)
+                }
+            }
+        )+
+    };
+}
+
+impl_float!(f32, f64);
+
+/// Round half away from zero into `u64`, which `core` can't do for `f64`.
+fn round(size: f64) -> Option<u64> {
+    let integer = f64_to_u64(size)?;
+    match size - integer as f64 >= 0.5 {
+        true => integer.checked_add(1),
+        false => Some(integer),
+    }
+}
@@ -0,0 +1,185 @@
+//! Integer-only formatting of bytesize
+//!
+//! The number is rounded exactly as formatting `bytes as f64 / unit as f64`
+//! with one decimal would do, by emulating the IEEE 754 operations on integers.
+
+/// Bytesize split as displayed, e.g. `1.5` and `MiB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Parts {
+    pub(crate) integer: u64,
+    /// `None` if displayed in bytes
+    pub(crate) tenth: Option<u8>,
+    pub(crate) symbol: &'static str,
+}
+
+impl Parts {
+    pub(crate) fn new(bytes: u64, base: u64, symbols: &'static [&'static str]) -> Self {
+        if bytes < base || symbols.is_empty() {
+            return Self {
+                integer: bytes,
+                tenth: None,
+                symbol: "B",
+            };
+        }
+
+        // The largest unit not exceeding the size
+        let mut unit = base;
+        let mut exp = 1;
+        while exp < symbols.len()
+            && let Some(next) = unit.checked_mul(base)
+            && next <= bytes
+        {
+            unit = next;
+            exp += 1;
+        }
+
+        let tenths = tenths(bytes, unit);
+        Self {
+            integer: tenths / 10,
+            tenth: Some((tenths % 10) as u8),
+            symbol: symbols[exp - 1],
+        }
+    }
+}
+
+/// Number of bits in the significand of `f64`
+const PRECISION: u32 = 53;
+
+/// `f64` as `significand * 2^exponent`, where the significand has exactly 53 bits
+#[derive(Debug, Clone, Copy)]
+struct Float {
+    significand: u128,
+    exponent: i32,
+}
+
+impl Float {
+    /// `n as f64` of a positive integer
+    fn from_u64(n: u64) -> Self {
+        Self::round(n as u128, 0, false)
+    }
+
+    /// Round `n * 2^exponent` to 53 bits, ties to even, where `sticky` tells
+    /// whether anything nonzero was truncated below `n`.
+    fn round(n: u128, exponent: i32, sticky: bool) -> Self {
+        let bits = u128::BITS - n.leading_zeros();
+        if bits <= PRECISION {
+            let shift = PRECISION - bits;
+            return Self {
+                significand: n << shift,
+                exponent: exponent - shift as i32,
+            };
+        }
+
+        let shift = bits - PRECISION;
+        let mut significand = n >> shift;
+        let rest = n & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        if rest > half || (rest == half && (sticky || significand & 1 == 1)) {
+            significand += 1;
+        }
+
+        let mut exponent = exponent + shift as i32;
+        if significand >> PRECISION != 0 {
+            significand >>= 1;
+            exponent += 1;
+        }
+        Self {
+            significand,
+            exponent,
+        }
+    }
+
+    /// `self / rhs`, correctly rounded
+    fn div(self, rhs: Self) -> Self {
+        // Both significands have 53 bits, so the quotient has at least 74 bits
+        const SHIFT: u32 = u128::BITS - PRECISION - 1;
+        let dividend = self.significand << SHIFT;
+        let quotient = dividend / rhs.significand;
+        let sticky = !dividend.is_multiple_of(rhs.significand);
+        Self::round(
+            quotient,
+            self.exponent - rhs.exponent - SHIFT as i32,
+            sticky,
+        )
+    }
+}
+
+/// `bytes as f64 / unit as f64` in tenths, rounded half to even as `{:.1}` does
+fn tenths(bytes: u64, unit: u64) -> u64 {
+    let quotient = Float::from_u64(bytes).div(Float::from_u64(unit));
+    let scaled = quotient.significand * 10;
+
+    if quotient.exponent >= 0 {
+        return (scaled << quotient.exponent) as u64;
+    }
+
+    let shift = quotient.exponent.unsigned_abs();
+    if shift >= u128::BITS {
+        return 0;
+    }
+    let integer = scaled >> shift;
+    let rest = scaled & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    let round_up = rest > half || (rest == half && integer & 1 == 1);
+    (integer + u128::from(round_up)) as u64
+}
+
+/// Writer of ASCII into a fixed buffer
+pub(crate) struct Cursor<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+}
+
+impl<'b> Cursor<'b> {
+    #[inline]
+    pub(crate) fn new(buf: &'b mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    #[inline]
+    pub(crate) fn push_str(&mut self, s: &str) -> Result<(), core::fmt::Error> {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+
+    pub(crate) fn push_u64(&mut self, mut n: u64) -> Result<(), core::fmt::Error> {
+        let mut digits = [0; 20];
+        let mut i = digits.len();
+        loop {
+            i -= 1;
+            digits[i] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+
+        let end = self.len + digits.len() - i;
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(&digits[i..]);
+        self.len = end;
+        Ok(())
+    }
+
+    pub(crate) fn push_parts(&mut self, parts: &Parts) -> Result<(), core::fmt::Error> {
+        self.push_u64(parts.integer)?;
+        if let Some(tenth) = parts.tenth {
+            self.push_str(".")?;
+            self.push_u64(tenth.into())?;
+        }
+        self.push_str(parts.symbol)
+    }
+
+    #[inline]
+    pub(crate) fn into_str(self) -> &'b str {
+        // Only complete `str`s and ASCII digits are written
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
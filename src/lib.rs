@@ -1,14 +1,55 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 #[cfg(test)]
 mod tests;
 
 #[cfg(feature = "serde")]
-mod serde;
+pub mod serde;
+
+#[cfg(feature = "nom")]
+pub mod nom;
+
+#[cfg(feature = "winnow")]
+pub mod winnow;
+
+mod approx;
+pub mod dialect;
+mod error;
+mod ext;
+mod format;
+mod limit;
+mod macros;
+mod options;
+mod parsed;
+pub mod quantity;
+mod relative;
+pub mod scan;
+pub mod sort;
+mod system;
+
+pub use approx::{Approx, Phrases};
+pub use dialect::{Dialect, DialectDisplay};
+pub use error::{ConversionError, Error, ErrorKind};
+pub use ext::ByteSizeExt;
+pub use limit::ByteLimit;
+pub use options::ParseOptions;
+pub use parsed::ParsedByteSize;
+pub use quantity::Quantity;
+pub use relative::{Clamp, RelativeByteSize};
+pub use system::{Iec, Jedec, Si, System, UnitSystem};
+
+use error::suggest_unit;
+use format::{Cursor, Parts};
+use system::jedec_unit_bytes;
 
 use core::{
-    num::{IntErrorKind, ParseFloatError, ParseIntError},
-    ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
     str::FromStr,
 };
 
@@ -28,77 +69,327 @@ pub const PB: u64 = 10u64.pow(15);
 /// 1 exabyte
 pub const EB: u64 = 10u64.pow(18);
 
+/// 1 kibibyte
+pub const KIB: u64 = 2u64.pow(10);
+/// 1 mebibyte
+pub const MIB: u64 = 2u64.pow(20);
+/// 1 gibibyte
+pub const GIB: u64 = 2u64.pow(30);
+/// 1 tebibyte
+pub const TIB: u64 = 2u64.pow(40);
+/// 1 pebibyte
+pub const PIB: u64 = 2u64.pow(50);
+/// 1 exbibyte
+pub const EIB: u64 = 2u64.pow(60);
+
+/// Bytesize formatted in the units of system `S`
+///
+/// Without `S`, the system is a [`System`] selected at runtime.
+/// Values are compared by bytes only.
+///
+/// [`ByteSizeSi`](type@ByteSizeSi), [`ByteSizeIec`](type@ByteSizeIec) and
+/// [`ByteSizeJedec`](type@ByteSizeJedec) are aliases of it, so patterns match
+/// `ByteSize(n, _)` rather than e.g. `ByteSizeIec(n)`:
+///
+/// ```
+/// use typed_bytesize::{ByteSize, ByteSizeIec};
+///
+/// let ByteSize(n, _) = ByteSizeIec::kib(2);
+/// assert_eq!(n, 2048);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct ByteSize<S = System>(pub u64, pub S);
+
 /// Decimal prefix bytesize
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
-pub struct ByteSizeSi(pub u64);
+pub type ByteSizeSi = ByteSize<Si>;
 
-impl ByteSizeSi {
-    const PREFIXES: &'static [char] = &['k', 'M', 'G', 'T', 'P', 'E'];
+/// Construct [`ByteSizeSi`](type@ByteSizeSi) of bytes.
+#[allow(non_snake_case)]
+#[inline(always)]
+pub const fn ByteSizeSi(n: u64) -> ByteSizeSi {
+    ByteSize(n, Si)
+}
+
+/// Binary prefix bytesize
+pub type ByteSizeIec = ByteSize<Iec>;
+
+/// Construct [`ByteSizeIec`](type@ByteSizeIec) of bytes.
+#[allow(non_snake_case)]
+#[inline(always)]
+pub const fn ByteSizeIec(n: u64) -> ByteSizeIec {
+    ByteSize(n, Iec)
+}
+
+/// JEDEC memory notation bytesize, where `KB` means 1024 bytes
+pub type ByteSizeJedec = ByteSize<Jedec>;
+
+/// Construct [`ByteSizeJedec`](type@ByteSizeJedec) of bytes.
+#[allow(non_snake_case)]
+#[inline(always)]
+pub const fn ByteSizeJedec(n: u64) -> ByteSizeJedec {
+    ByteSize(n, Jedec)
+}
+
+impl<S> PartialEq for ByteSize<S> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<S> Eq for ByteSize<S> {}
+
+impl<S> PartialOrd for ByteSize<S> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for ByteSize<S> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
 }
 
-impl core::fmt::Display for ByteSizeSi {
+impl<S> Hash for ByteSize<S> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<S: UnitSystem + core::fmt::Debug> core::fmt::Debug for ByteSize<S> {
+    /// Print as the alias of the system if any, e.g. `ByteSizeIec(5)`.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.1.alias() {
+            Some(alias) => f.debug_tuple(alias).field(&self.0).finish(),
+            None => f
+                .debug_tuple("ByteSize")
+                .field(&self.0)
+                .field(&self.1)
+                .finish(),
+        }
+    }
+}
+
+impl<S: UnitSystem> core::fmt::Display for ByteSize<S> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        if self.0 < KB {
-            write!(f, "{}B", self.0)
-        } else {
-            let size = self.0 as f64;
-            let exp = (size.log(KB as f64) as usize).min(Self::PREFIXES.len());
-            let number = size / (KB.pow(exp as u32) as f64);
-            let prefix = Self::PREFIXES[exp - 1];
-
-            write!(f, "{number:.1}{prefix}B")
+        let parts = Parts::new(self.0, self.1.base(), self.1.symbols());
+
+        match parts.tenth {
+            Some(tenth) => write!(f, "{}.{tenth}{}", parts.integer, parts.symbol),
+            None => write!(f, "{}{}", parts.integer, parts.symbol),
         }
     }
 }
 
-impl FromStr for ByteSizeSi {
+impl<S: UnitSystem + Default> FromStr for ByteSize<S> {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_byte_size(s).map(Self)
+        Self::parse_with(s, &ParseOptions::new())
+    }
+}
+
+impl<S: UnitSystem> ByteSize<S> {
+    /// Bytesize of `n` bytes in `system`
+    #[inline(always)]
+    pub const fn with_system(n: u64, system: S) -> Self {
+        Self(n, system)
+    }
+
+    /// Unit system of the bytesize
+    #[inline(always)]
+    pub fn system(&self) -> S {
+        self.1
+    }
+
+    /// Convert into another unit system.
+    #[inline(always)]
+    pub fn in_system<T: UnitSystem>(self, system: T) -> ByteSize<T> {
+        ByteSize(self.0, system)
+    }
+
+    /// Format into `buf` without allocating, as [`Display`](core::fmt::Display) does.
+    ///
+    /// 16 bytes always suffice for [`Si`], [`Iec`] and [`Jedec`]; a shorter
+    /// buffer fails if the output doesn't fit.
+    ///
+    /// ```
+    /// use typed_bytesize::ByteSizeIec;
+    ///
+    /// let mut buf = [0; 16];
+    /// assert_eq!(ByteSizeIec::mib(1536).format_to(&mut buf), Ok("1.5GiB"));
+    /// ```
+    pub fn format_to<'b>(&self, buf: &'b mut [u8]) -> Result<&'b str, core::fmt::Error> {
+        let parts = Parts::new(self.0, self.1.base(), self.1.symbols());
+        let mut cursor = Cursor::new(buf);
+        cursor.push_parts(&parts)?;
+        Ok(cursor.into_str())
+    }
+
+    /// Display approximately in English, e.g. `about 2 MB` or `less than 1 KiB`.
+    ///
+    /// ```
+    /// use typed_bytesize::{ByteSizeIec, ByteSizeSi};
+    ///
+    /// assert_eq!(ByteSizeIec(512).approx().to_string(), "less than 1 KiB");
+    /// assert_eq!(ByteSizeSi::kb(1950).approx().to_string(), "about 2 MB");
+    /// assert_eq!(ByteSizeSi::gb(1200).approx().to_string(), "over 1 TB");
+    /// ```
+    #[inline]
+    pub fn approx(self) -> Approx<'static, S> {
+        self.approx_with(Phrases::new())
+    }
+
+    /// Display approximately in the given phrases.
+    #[inline]
+    pub fn approx_with(self, phrases: Phrases<'_>) -> Approx<'_, S> {
+        Approx {
+            bytes: self.0,
+            system: self.1,
+            phrases,
+        }
+    }
+
+    /// Format in the syntax of `dialect`.
+    #[inline]
+    pub fn display_dialect(self, dialect: Dialect) -> DialectDisplay {
+        DialectDisplay {
+            dialect,
+            bytes: self.0,
+            binary: self.1.base() == KIB,
+        }
+    }
+}
+
+impl<S: UnitSystem + Default> ByteSize<S> {
+    #[inline]
+    pub fn b(n: impl Into<u64>) -> Self {
+        Self(n.into(), S::default())
+    }
+
+    /// Parse bytesize, interpreting bare numbers in `unit` rather than bytes.
+    #[inline]
+    pub fn parse_with_default_unit(s: &str, unit: Unit) -> Result<Self, Error> {
+        Self::parse_with(s, &ParseOptions::new().default_unit(Some(unit)))
+    }
+
+    /// Parse bytesize under the given options.
+    #[inline]
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, Error> {
+        let system = S::default();
+        parse_byte_size_with(s, options, &system).map(|n| Self(n, system))
+    }
+
+    /// Parse bytesize under [`ParseOptions::strict`].
+    #[inline]
+    pub fn parse_strict(s: &str) -> Result<Self, Error> {
+        Self::parse_with(s, &ParseOptions::strict())
+    }
+
+    /// Parse bytesize in the syntax of `dialect`.
+    #[inline]
+    pub fn parse_dialect(s: &str, dialect: Dialect) -> Result<Self, Error> {
+        dialect.parse(s).map(Self::b)
+    }
+
+    /// Parse bytesize from bytes, e.g. of raw logs, as [`FromStr`] does.
+    ///
+    /// Invalid UTF-8 is reported as [`ErrorKind::Invalid`] at its position.
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match core::str::from_utf8(bytes) {
+            Ok(s) => s.parse(),
+            Err(e) => {
+                let valid = core::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default();
+                Err(Error::new(
+                    ErrorKind::Invalid,
+                    valid.len()..valid.len(),
+                    valid,
+                ))
+            }
+        }
+    }
+
+    /// Parse bytesize from an OS string, e.g. of [`std::env::args_os`], as [`FromStr`] does.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn parse_os_str(s: &std::ffi::OsStr) -> Result<Self, Error> {
+        Self::parse_bytes(s.as_encoded_bytes())
+    }
+
+    /// Parse bytesize at the start of `s`, returning it with the rest.
+    ///
+    /// The unit is the whole word following the number, and the size is in bytes
+    /// if the word isn't a unit:
+    ///
+    /// ```
+    /// use typed_bytesize::ByteSizeIec;
+    ///
+    /// assert_eq!(ByteSizeIec::parse_prefix("1.5GiB, 2GiB"), Ok((ByteSizeIec::mib(1536), ", 2GiB")));
+    /// assert_eq!(ByteSizeIec::parse_prefix("512 bytes"), Ok((ByteSizeIec(512), " bytes")));
+    /// ```
+    pub fn parse_prefix(s: &str) -> Result<(Self, &str), Error> {
+        let system = S::default();
+        let (n, len) = parse_prefix_with(s, &ParseOptions::new(), &system)?;
+        Ok((Self(n, system), &s[len..]))
+    }
+}
+
+impl ByteSize {
+    /// Bytesize of `n` bytes in the runtime selected `system`
+    #[inline(always)]
+    pub const fn new(n: u64, system: System) -> Self {
+        Self(n, system)
     }
 }
 
 impl ByteSizeSi {
     /// The smallest value that can be represented.
-    pub const MIN: Self = Self(u64::MIN);
+    pub const MIN: Self = ByteSizeSi(u64::MIN);
 
     /// The largest value that can be represented.
-    pub const MAX: Self = Self(u64::MAX);
+    pub const MAX: Self = ByteSizeSi(u64::MAX);
 
+    /// Parse bytesize as [`FromStr`] does, in const contexts.
+    ///
+    /// It panics on invalid or overflowing input, which fails compilation in constants.
+    /// See also [`bytesize!`].
     #[inline]
-    pub fn b(n: impl Into<u64>) -> Self {
-        Self(n.into())
+    pub const fn parse_const(s: &str) -> Self {
+        ByteSizeSi(unwrap_const(parse_byte_size_const(s, System::Si)))
     }
 
     #[inline(always)]
     pub const fn kb(n: u64) -> Self {
-        Self(n * KB)
+        ByteSizeSi(n * KB)
     }
 
     #[inline(always)]
     pub const fn mb(n: u64) -> Self {
-        Self(n * MB)
+        ByteSizeSi(n * MB)
     }
 
     #[inline(always)]
     pub const fn gb(n: u64) -> Self {
-        Self(n * GB)
+        ByteSizeSi(n * GB)
     }
 
     #[inline(always)]
     pub const fn tb(n: u64) -> Self {
-        Self(n * TB)
+        ByteSizeSi(n * TB)
     }
 
     #[inline(always)]
     pub const fn pb(n: u64) -> Self {
-        Self(n * PB)
+        ByteSizeSi(n * PB)
     }
 
     #[inline(always)]
     pub const fn eb(n: u64) -> Self {
-        Self(n * EB)
+        ByteSizeSi(n * EB)
     }
 
     /// Convert into binary prefix unit
@@ -107,6 +398,12 @@ impl ByteSizeSi {
         ByteSizeIec(self.0)
     }
 
+    /// Convert into JEDEC memory notation
+    #[inline(always)]
+    pub const fn jedec(self) -> ByteSizeJedec {
+        ByteSizeJedec(self.0)
+    }
+
     #[inline(always)]
     pub const fn as_kb(self) -> f64 {
         self.0 as f64 / KB as f64
@@ -138,156 +435,139 @@ impl ByteSizeSi {
     }
 }
 
-impl From<u64> for ByteSizeSi {
-    fn from(n: u64) -> Self {
-        Self(n)
-    }
-}
+impl ByteSizeIec {
+    /// The smallest value that can be represented.
+    pub const MIN: Self = ByteSizeIec(u64::MIN);
 
-impl From<ByteSizeSi> for u64 {
-    fn from(bs: ByteSizeSi) -> Self {
-        bs.0
-    }
-}
+    /// The largest value that can be represented.
+    pub const MAX: Self = ByteSizeIec(u64::MAX);
 
-impl Add for ByteSizeSi {
-    type Output = Self;
+    /// Parse bytesize as [`FromStr`] does, in const contexts.
+    ///
+    /// It panics on invalid or overflowing input, which fails compilation in constants.
+    /// See also [`bytesize!`].
+    #[inline]
+    pub const fn parse_const(s: &str) -> Self {
+        ByteSizeIec(unwrap_const(parse_byte_size_const(s, System::Iec)))
+    }
 
     #[inline(always)]
-    fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0)
+    pub const fn kib(n: u64) -> Self {
+        ByteSizeIec(n * KIB)
     }
-}
 
-impl AddAssign for ByteSizeSi {
     #[inline(always)]
-    fn add_assign(&mut self, rhs: Self) {
-        self.0 += rhs.0;
+    pub const fn mib(n: u64) -> Self {
+        ByteSizeIec(n * MIB)
     }
-}
 
-impl Sub for ByteSizeSi {
-    type Output = Self;
+    #[inline(always)]
+    pub const fn gib(n: u64) -> Self {
+        ByteSizeIec(n * GIB)
+    }
 
     #[inline(always)]
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0)
+    pub const fn tib(n: u64) -> Self {
+        ByteSizeIec(n * TIB)
     }
-}
 
-impl SubAssign for ByteSizeSi {
     #[inline(always)]
-    fn sub_assign(&mut self, rhs: Self) {
-        self.0 -= rhs.0;
+    pub const fn pib(n: u64) -> Self {
+        ByteSizeIec(n * PIB)
     }
-}
 
-impl<T> Mul<T> for ByteSizeSi
-where
-    T: Into<u64>,
-{
-    type Output = Self;
+    #[inline(always)]
+    pub const fn eib(n: u64) -> Self {
+        ByteSizeIec(n * EIB)
+    }
 
-    #[inline]
-    fn mul(self, rhs: T) -> Self::Output {
-        Self(self.0 * rhs.into())
+    /// Convert into decimal prefix unit
+    #[inline(always)]
+    pub const fn si(self) -> ByteSizeSi {
+        ByteSizeSi(self.0)
     }
-}
 
-impl<T> MulAssign<T> for ByteSizeSi
-where
-    T: Into<u64>,
-{
-    #[inline]
-    fn mul_assign(&mut self, rhs: T) {
-        self.0 *= rhs.into();
+    /// Convert into JEDEC memory notation
+    #[inline(always)]
+    pub const fn jedec(self) -> ByteSizeJedec {
+        ByteSizeJedec(self.0)
     }
-}
 
-/// 1 kibibyte
-pub const KIB: u64 = 2u64.pow(10);
-/// 1 mebibyte
-pub const MIB: u64 = 2u64.pow(20);
-/// 1 gibibyte
-pub const GIB: u64 = 2u64.pow(30);
-/// 1 tebibyte
-pub const TIB: u64 = 2u64.pow(40);
-/// 1 pebibyte
-pub const PIB: u64 = 2u64.pow(50);
-/// 1 exbibyte
-pub const EIB: u64 = 2u64.pow(60);
+    #[inline(always)]
+    pub const fn as_kib(self) -> f64 {
+        self.0 as f64 / KIB as f64
+    }
 
-/// Binary prefix bytesize
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
-pub struct ByteSizeIec(pub u64);
+    #[inline(always)]
+    pub const fn as_mib(self) -> f64 {
+        self.0 as f64 / MIB as f64
+    }
 
-impl ByteSizeIec {
-    const PREFIXES: &'static [char] = &['K', 'M', 'G', 'T', 'P', 'E'];
-}
+    #[inline(always)]
+    pub const fn as_gib(self) -> f64 {
+        self.0 as f64 / GIB as f64
+    }
 
-impl core::fmt::Display for ByteSizeIec {
-    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        if self.0 < KIB {
-            write!(f, "{}B", self.0)
-        } else {
-            let size = self.0 as f64;
-            let exp = (size.log(KIB as f64) as usize).min(Self::PREFIXES.len());
-            let number = size / (KIB.pow(exp as u32) as f64);
-            let prefix = Self::PREFIXES[exp - 1];
-
-            write!(f, "{number:.1}{prefix}iB")
-        }
+    #[inline(always)]
+    pub const fn as_tib(self) -> f64 {
+        self.0 as f64 / TIB as f64
     }
-}
 
-impl FromStr for ByteSizeIec {
-    type Err = Error;
+    #[inline(always)]
+    pub const fn as_pib(self) -> f64 {
+        self.0 as f64 / PIB as f64
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_byte_size(s).map(Self)
+    #[inline(always)]
+    pub const fn as_eib(self) -> f64 {
+        self.0 as f64 / EIB as f64
     }
 }
 
-impl ByteSizeIec {
+impl ByteSizeJedec {
     /// The smallest value that can be represented.
-    pub const MIN: Self = Self(u64::MIN);
+    pub const MIN: Self = ByteSizeJedec(u64::MIN);
 
     /// The largest value that can be represented.
-    pub const MAX: Self = Self(u64::MAX);
+    pub const MAX: Self = ByteSizeJedec(u64::MAX);
 
+    /// Parse bytesize as [`FromStr`] does, in const contexts.
+    ///
+    /// It panics on invalid or overflowing input, which fails compilation in constants.
+    /// See also [`bytesize!`].
     #[inline]
-    pub fn b(n: impl Into<u64>) -> Self {
-        Self(n.into())
+    pub const fn parse_const(s: &str) -> Self {
+        ByteSizeJedec(unwrap_const(parse_byte_size_const(s, System::Jedec)))
     }
 
     #[inline(always)]
-    pub const fn kib(n: u64) -> Self {
-        Self(n * KIB)
+    pub const fn kb(n: u64) -> Self {
+        ByteSizeJedec(n * KIB)
     }
 
     #[inline(always)]
-    pub const fn mib(n: u64) -> Self {
-        Self(n * MIB)
+    pub const fn mb(n: u64) -> Self {
+        ByteSizeJedec(n * MIB)
     }
 
     #[inline(always)]
-    pub const fn gib(n: u64) -> Self {
-        Self(n * GIB)
+    pub const fn gb(n: u64) -> Self {
+        ByteSizeJedec(n * GIB)
     }
 
     #[inline(always)]
-    pub const fn tib(n: u64) -> Self {
-        Self(n * TIB)
+    pub const fn tb(n: u64) -> Self {
+        ByteSizeJedec(n * TIB)
     }
 
     #[inline(always)]
-    pub const fn pib(n: u64) -> Self {
-        Self(n * PIB)
+    pub const fn pb(n: u64) -> Self {
+        ByteSizeJedec(n * PIB)
     }
 
     #[inline(always)]
-    pub const fn eib(n: u64) -> Self {
-        Self(n * EIB)
+    pub const fn eb(n: u64) -> Self {
+        ByteSizeJedec(n * EIB)
     }
 
     /// Convert into decimal prefix unit
@@ -296,151 +576,742 @@ impl ByteSizeIec {
         ByteSizeSi(self.0)
     }
 
+    /// Convert into binary prefix unit
     #[inline(always)]
-    pub const fn as_kib(self) -> f64 {
+    pub const fn iec(self) -> ByteSizeIec {
+        ByteSizeIec(self.0)
+    }
+
+    #[inline(always)]
+    pub const fn as_kb(self) -> f64 {
         self.0 as f64 / KIB as f64
     }
 
     #[inline(always)]
-    pub const fn as_mib(self) -> f64 {
+    pub const fn as_mb(self) -> f64 {
         self.0 as f64 / MIB as f64
     }
 
     #[inline(always)]
-    pub const fn as_gib(self) -> f64 {
+    pub const fn as_gb(self) -> f64 {
         self.0 as f64 / GIB as f64
     }
 
     #[inline(always)]
-    pub const fn as_tib(self) -> f64 {
+    pub const fn as_tb(self) -> f64 {
         self.0 as f64 / TIB as f64
     }
 
     #[inline(always)]
-    pub const fn as_pib(self) -> f64 {
+    pub const fn as_pb(self) -> f64 {
         self.0 as f64 / PIB as f64
     }
 
     #[inline(always)]
-    pub const fn as_eib(self) -> f64 {
+    pub const fn as_eb(self) -> f64 {
         self.0 as f64 / EIB as f64
     }
 }
 
-impl From<u64> for ByteSizeIec {
+impl<S: UnitSystem + Default> From<u64> for ByteSize<S> {
     fn from(n: u64) -> Self {
-        Self(n)
+        Self::b(n)
     }
 }
 
-impl From<ByteSizeIec> for u64 {
-    fn from(bs: ByteSizeIec) -> Self {
+impl<S> From<ByteSize<S>> for u64 {
+    fn from(bs: ByteSize<S>) -> Self {
         bs.0
     }
 }
 
-impl Add for ByteSizeIec {
+macro_rules! impl_from_int {
+    ($($ty:ty),+) => {
+        $(
+            impl<S: UnitSystem + Default> From<$ty> for ByteSize<S> {
+                fn from(n: $ty) -> Self {
+                    Self::b(n)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_int!(u8, u16, u32);
+
+macro_rules! impl_try_from_int {
+    ($($ty:ty),+) => {
+        $(
+            impl<S: UnitSystem + Default> TryFrom<$ty> for ByteSize<S> {
+                type Error = ConversionError;
+
+                fn try_from(n: $ty) -> Result<Self, Self::Error> {
+                    match u64::try_from(n) {
+                        Ok(n) => Ok(Self::b(n)),
+                        #[allow(unused_comparisons)]
+                        Err(_) if n < 0 => Err(ConversionError::new(ErrorKind::Negative)),
+                        Err(_) => Err(ConversionError::new(ErrorKind::Overflow)),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_int!(usize, i32, i64, u128, i128);
+
+impl<S: UnitSystem + Default> TryFrom<f64> for ByteSize<S> {
+    type Error = ConversionError;
+
+    /// Convert from a whole number of bytes.
+    fn try_from(n: f64) -> Result<Self, Self::Error> {
+        let kind = match n {
+            _ if n.is_nan() => ErrorKind::BadNumber,
+            _ if n < 0.0 => ErrorKind::Negative,
+            _ => match f64_to_u64(n) {
+                None => ErrorKind::Overflow,
+                Some(bytes) if bytes as f64 != n => ErrorKind::FractionalBytes,
+                Some(bytes) => return Ok(Self::b(bytes)),
+            },
+        };
+        Err(ConversionError::new(kind))
+    }
+}
+
+macro_rules! impl_try_into_int {
+    ($($ty:ty),+) => {
+        $(
+            impl<S> TryFrom<ByteSize<S>> for $ty {
+                type Error = ConversionError;
+
+                fn try_from(bs: ByteSize<S>) -> Result<Self, Self::Error> {
+                    <$ty>::try_from(bs.0).map_err(|_| ConversionError::new(ErrorKind::Overflow))
+                }
+            }
+        )+
+    };
+}
+
+impl_try_into_int!(usize, u32, i64);
+
+impl<S> Add for ByteSize<S> {
     type Output = Self;
 
     #[inline(always)]
     fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0)
+        Self(self.0 + rhs.0, self.1)
     }
 }
 
-impl AddAssign for ByteSizeIec {
+impl<S> AddAssign for ByteSize<S> {
     #[inline(always)]
     fn add_assign(&mut self, rhs: Self) {
         self.0 += rhs.0;
     }
 }
 
-impl Sub for ByteSizeIec {
+impl<S> Sub for ByteSize<S> {
     type Output = Self;
 
     #[inline(always)]
     fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0)
+        Self(self.0 - rhs.0, self.1)
     }
 }
 
-impl SubAssign for ByteSizeIec {
+impl<S> SubAssign for ByteSize<S> {
     #[inline(always)]
     fn sub_assign(&mut self, rhs: Self) {
         self.0 -= rhs.0;
     }
 }
 
-impl<T> Mul<T> for ByteSizeIec
-where
-    T: Into<u64>,
-{
-    type Output = Self;
+macro_rules! impl_scalar {
+    ($($ty:ty),+) => {
+        $(
+            impl<S> Mul<$ty> for ByteSize<S> {
+                type Output = Self;
+
+                #[inline]
+                fn mul(self, rhs: $ty) -> Self::Output {
+                    Self(self.0 * rhs as u64, self.1)
+                }
+            }
+
+            impl<S> MulAssign<$ty> for ByteSize<S> {
+                #[inline]
+                fn mul_assign(&mut self, rhs: $ty) {
+                    self.0 *= rhs as u64;
+                }
+            }
+
+            impl<S> Div<$ty> for ByteSize<S> {
+                type Output = Self;
+
+                #[inline]
+                fn div(self, rhs: $ty) -> Self::Output {
+                    Self(self.0 / rhs as u64, self.1)
+                }
+            }
+
+            impl<S> DivAssign<$ty> for ByteSize<S> {
+                #[inline]
+                fn div_assign(&mut self, rhs: $ty) {
+                    self.0 /= rhs as u64;
+                }
+            }
+        )+
+    };
+}
 
-    #[inline]
-    fn mul(self, rhs: T) -> Self::Output {
-        Self(self.0 * rhs.into())
-    }
+impl_scalar!(u8, u16, u32, u64, usize);
+
+fn parse_byte_size(input: &str) -> Result<u64, Error> {
+    parse_byte_size_with(input, &ParseOptions::new(), &Iec)
 }
 
-impl<T> MulAssign<T> for ByteSizeIec
-where
-    T: Into<u64>,
-{
-    #[inline]
-    fn mul_assign(&mut self, rhs: T) {
-        self.0 *= rhs.into();
+/// Parse bytesize under `options` in the units of `system`.
+fn parse_byte_size_with(
+    input: &str,
+    options: &ParseOptions,
+    system: &impl UnitSystem,
+) -> Result<u64, Error> {
+    let error = |kind, span| Error::new(kind, span, input);
+
+    if input.is_empty() {
+        return Err(error(ErrorKind::Empty, 0..0));
     }
-}
 
-fn parse_byte_size(input: &str) -> Result<u64, Error> {
-    let Some(i) = input.find(|c: char| !c.is_ascii_digit()) else {
-        return input.parse::<u64>().map_err(Into::into);
-    };
+    let i = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
 
     if i == 0 {
-        return Err(Error::Invalid);
+        return Err(match input.strip_prefix('-') {
+            Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => {
+                error(ErrorKind::Negative, 0..1)
+            }
+            _ => error(
+                ErrorKind::BadNumber,
+                0..input.find(' ').unwrap_or(input.len()),
+            ),
+        });
     }
 
     let (integer, rest) = input.split_at(i);
 
-    if let Some(rest) = rest.strip_prefix('.') {
-        let i = i
-            + 1
-            + rest
+    let fraction = match rest.strip_prefix('.') {
+        Some(rest) => {
+            let digits = rest
                 .find(|c: char| !c.is_ascii_digit())
-                .filter(|n| *n > 0)
-                .ok_or(Error::Invalid)?;
+                .unwrap_or(rest.len());
+            if digits == 0 {
+                return Err(error(ErrorKind::BadNumber, 0..i + 1));
+            }
+            Some(&rest[..digits])
+        }
+        None => None,
+    };
+    let number_end = i + fraction.map_or(0, |fraction| fraction.len() + 1);
+
+    let unit = if number_end == input.len() {
+        match options.default_unit.unwrap_or(Unit::B) {
+            Unit::B if fraction.is_some() => {
+                return Err(error(ErrorKind::FractionalBytes, 0..number_end));
+            }
+            unit => unit.bytes(),
+        }
+    } else {
+        let start = input.len() - input[number_end..].trim_start_matches(' ').len();
+        let end = input[start..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .map_or(input.len(), |n| start + n);
+        let token = &input[start..end];
+
+        if token.is_empty() {
+            return Err(match input[start..].chars().next() {
+                Some(c) => error(ErrorKind::BadNumber, 0..start + c.len_utf8()),
+                None => error(ErrorKind::TrailingInput, number_end..input.len()),
+            });
+        }
+
+        let unit = parse_unit_with(token, options, system).map_err(|kind| {
+            let units = Unit::ALL
+                .into_iter()
+                .filter(|unit| options.foreign_units || system.owns(*unit))
+                .map(Unit::symbol);
+            let suggestion = suggest_unit(token, system.symbols().iter().copied().chain(units));
+            error(kind, start..end).with_suggestion(suggestion)
+        })?;
+
+        if end < input.len() {
+            return Err(error(ErrorKind::TrailingInput, end..input.len()));
+        }
+        unit
+    };
+
+    let overflow = || error(ErrorKind::Overflow, 0..input.len());
+
+    if fraction.is_none() {
+        return integer
+            .parse::<u64>()
+            .ok()
+            .and_then(|integer| integer.checked_mul(unit))
+            .ok_or_else(overflow);
+    }
+
+    match fraction_bytes(&input.as_bytes()[..number_end], unit) {
+        Some((_, false)) if options.exact => Err(error(ErrorKind::FractionalBytes, 0..input.len())),
+        Some((size, _)) => Ok(size),
+        None if options.exact => Err(overflow()),
+        // Oversized fractions saturate
+        None => Ok(u64::MAX),
+    }
+}
+
+/// Parse the number at the start of `input`, like `1` or `1.5`, returning its length.
+fn parse_number_prefix(input: &str) -> Result<usize, Error> {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
 
-        let (float, rest) = input.split_at(i);
-        let float = float.parse::<f64>()?;
-        let unit = parse_unit(rest.trim_start_matches(' '))?;
-        Ok((float * unit as f64) as u64)
+    let integer = digits(input);
+    if integer == 0 {
+        // Always fails, with the error of a malformed number
+        return parse_byte_size(input).map(|_| 0);
+    }
+    match input[integer..].strip_prefix('.').map(digits) {
+        Some(fraction) if fraction > 0 => Ok(integer + 1 + fraction),
+        _ => Ok(integer),
+    }
+}
+
+/// Parse the unit word at the start of `input`, returning it with its length.
+#[cfg(any(feature = "nom", feature = "winnow"))]
+fn parse_unit_prefix(input: &str) -> Result<(Unit, usize), Error> {
+    let len = input.bytes().take_while(u8::is_ascii_alphabetic).count();
+    let token = &input[..len];
+    Unit::from_str(token)
+        .map(|unit| (unit, len))
+        .map_err(|e| e.within(input, token))
+}
+
+/// Parse bytesize at the start of `input`, returning it with the length parsed.
+fn parse_prefix_with(
+    input: &str,
+    options: &ParseOptions,
+    system: &impl UnitSystem,
+) -> Result<(u64, usize), Error> {
+    let number_end = parse_number_prefix(input)?;
+    let bytes = input.as_bytes();
+
+    let unit_start = number_end
+        + bytes[number_end..]
+            .iter()
+            .take_while(|b| **b == b' ')
+            .count();
+    let unit_end = unit_start
+        + bytes[unit_start..]
+            .iter()
+            .take_while(|b| b.is_ascii_alphabetic())
+            .count();
+
+    if unit_end > unit_start {
+        match parse_byte_size_with(&input[..unit_end], options, system) {
+            Err(e) if matches!(e.kind(), ErrorKind::UnknownUnit | ErrorKind::AmbiguousUnit) => {}
+            result => {
+                return result
+                    .map(|n| (n, unit_end))
+                    .map_err(|e| e.within(input, &input[..unit_end]));
+            }
+        }
+    }
+
+    parse_byte_size_with(&input[..number_end], options, system)
+        .map(|n| (n, number_end))
+        .map_err(|e| e.within(input, &input[..number_end]))
+}
+
+/// Const [`parse_byte_size`] in the units of `system`, which unlike it also
+/// rejects overflowing fractions.
+const fn parse_byte_size_const(input: &str, system: System) -> Result<u64, ErrorKind> {
+    /// End of the ASCII digits from `start`
+    const fn digits_end(bytes: &[u8], mut i: usize) -> usize {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    }
+
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+
+    if len == 0 {
+        return Err(ErrorKind::Empty);
+    }
+
+    let i = digits_end(bytes, 0);
+    if i == 0 {
+        return Err(match bytes {
+            [b'-', b'0'..=b'9', ..] => ErrorKind::Negative,
+            _ => ErrorKind::BadNumber,
+        });
+    }
+
+    let mut number_end = i;
+    if i < len && bytes[i] == b'.' {
+        number_end = digits_end(bytes, i + 1);
+        if number_end == i + 1 {
+            return Err(ErrorKind::BadNumber);
+        }
+    }
+    let has_fraction = number_end > i;
+
+    let unit = if number_end == len {
+        if has_fraction {
+            return Err(ErrorKind::FractionalBytes);
+        }
+        B
     } else {
-        let integer = integer.parse::<u64>()?;
-        let unit = parse_unit(rest.trim_start_matches(' '))?;
-        Ok(integer * unit)
+        let mut start = number_end;
+        while start < len && bytes[start] == b' ' {
+            start += 1;
+        }
+        let mut end = start;
+        while end < len && bytes[end].is_ascii_alphabetic() {
+            end += 1;
+        }
+        if end == start {
+            return Err(if start < len {
+                ErrorKind::BadNumber
+            } else {
+                ErrorKind::TrailingInput
+            });
+        }
+        let (_, rest) = input.split_at(start);
+        let (token, _) = rest.split_at(end - start);
+
+        let unit = match system {
+            System::Jedec if let Some(bytes) = jedec_unit_bytes(token) => bytes,
+            _ => match parse_unit(token) {
+                Some(unit) => unit.bytes(),
+                None => return Err(ErrorKind::UnknownUnit),
+            },
+        };
+
+        if end < len {
+            return Err(ErrorKind::TrailingInput);
+        }
+        unit
+    };
+
+    if !has_fraction {
+        let mut integer: u64 = 0;
+        let mut j = 0;
+        while j < number_end {
+            let digit = (bytes[j] - b'0') as u64;
+            integer = match integer.checked_mul(10) {
+                Some(n) if n <= u64::MAX - digit => n + digit,
+                _ => return Err(ErrorKind::Overflow),
+            };
+            j += 1;
+        }
+        return match integer.checked_mul(unit) {
+            Some(size) => Ok(size),
+            None => Err(ErrorKind::Overflow),
+        };
+    }
+
+    let (number, _) = bytes.split_at(number_end);
+    match fraction_bytes(number, unit) {
+        Some((size, _)) => Ok(size),
+        None => Err(ErrorKind::Overflow),
+    }
+}
+
+/// Bytes of a number with a fraction like `1.5` in `unit`, with whether they're whole,
+/// or `None` if overflowing.
+///
+/// Numbers of at most 2^53 in all digits and 22 fraction digits are rounded
+/// as `str::parse::<f64>` does before being multiplied by `unit`, while longer
+/// ones are computed exactly and truncated.
+const fn fraction_bytes(number: &[u8], unit: u64) -> Option<(u64, bool)> {
+    let mut point = 0;
+    while number[point] != b'.' {
+        point += 1;
+    }
+
+    // The integer part, and all digits as the significand of the number
+    let mut integer: u64 = 0;
+    let mut significand: u64 = 0;
+    let mut exact_digits = true;
+    let mut j = 0;
+    while j < number.len() {
+        if j != point {
+            let digit = (number[j] - b'0') as u64;
+            if j < point {
+                integer = match integer.checked_mul(10) {
+                    Some(n) if n <= u64::MAX - digit => n + digit,
+                    _ => return None,
+                };
+            }
+            match significand.checked_mul(10) {
+                Some(n) if exact_digits && n + digit <= 1 << f64::MANTISSA_DIGITS => {
+                    significand = n + digit;
+                }
+                _ => exact_digits = false,
+            }
+        }
+        j += 1;
+    }
+
+    let fraction_digits = number.len() - point - 1;
+    if exact_digits && fraction_digits <= 22 {
+        // Both operands are exact, so the number is rounded as `str::parse` does
+        let mut scale = 1.0;
+        let mut k = 0;
+        while k < fraction_digits {
+            scale *= 10.0;
+            k += 1;
+        }
+        let size = significand as f64 / scale * unit as f64;
+
+        return match f64_to_u64(size) {
+            Some(bytes) => Some((bytes, bytes as f64 == size)),
+            None => None,
+        };
+    }
+
+    // Bytes of the fraction from its last digit, where flooring each step
+    // floors the whole: ⌊(d·unit + y) / 10⌋ = ⌊(d·unit + ⌊y⌋) / 10⌋
+    let mut fraction: u128 = 0;
+    let mut whole = true;
+    let mut k = number.len();
+    while k > point + 1 {
+        k -= 1;
+        let n = (number[k] - b'0') as u128 * unit as u128 + fraction;
+        whole = whole && n.is_multiple_of(10);
+        fraction = n / 10;
+    }
+    let fraction = fraction as u64;
+
+    match integer.checked_mul(unit) {
+        Some(size) if size <= u64::MAX - fraction => Some((size + fraction, whole)),
+        _ => None,
+    }
+}
+
+/// Truncate `x` into `u64`, or `None` if it's NaN or out of range.
+pub(crate) const fn f64_to_u64(x: f64) -> Option<u64> {
+    // u64::MAX as f64 rounds up to 2^64, which is out of range
+    match x >= 0.0 && x < u64::MAX as f64 {
+        true => Some(x as u64),
+        false => None,
+    }
+}
+
+/// Unwrap the result of [`parse_byte_size_const`], panicking at compile time in const contexts.
+const fn unwrap_const(result: Result<u64, ErrorKind>) -> u64 {
+    match result {
+        Ok(size) => size,
+        Err(kind) => panic!("{}", kind.message()),
+    }
+}
+
+/// Bytes of a unit symbol under `options` in the units of `system`
+fn parse_unit_with(
+    input: &str,
+    options: &ParseOptions,
+    system: &impl UnitSystem,
+) -> Result<u64, ErrorKind> {
+    if !options.lowercase_b && input.ends_with('b') {
+        return Err(ErrorKind::AmbiguousUnit);
+    }
+
+    if let Some(bytes) = system.unit_bytes(input)
+        && (!options.case_sensitive || system.symbols().contains(&input))
+    {
+        return Ok(bytes);
+    }
+
+    let unit = if options.case_sensitive {
+        parse_unit_exact(input)
+    } else {
+        parse_unit(input)
+    }
+    .ok_or(ErrorKind::UnknownUnit)?;
+
+    if !options.bare_prefixes && unit != Unit::B && !input.ends_with(['B', 'b']) {
+        return Err(ErrorKind::UnknownUnit);
+    }
+    if !options.foreign_units && !system.owns(unit) {
+        return Err(ErrorKind::UnknownUnit);
+    }
+
+    Ok(unit.bytes())
+}
+
+const fn parse_unit(input: &str) -> Option<Unit> {
+    const SYMBOLS: [(&str, &str, Unit); 13] = [
+        ("b", "b", Unit::B),
+        // SI
+        ("k", "kb", Unit::KB),
+        ("m", "mb", Unit::MB),
+        ("g", "gb", Unit::GB),
+        ("t", "tb", Unit::TB),
+        ("p", "pb", Unit::PB),
+        ("e", "eb", Unit::EB),
+        // IEC 60027-2
+        ("ki", "kib", Unit::KiB),
+        ("mi", "mib", Unit::MiB),
+        ("gi", "gib", Unit::GiB),
+        ("ti", "tib", Unit::TiB),
+        ("pi", "pib", Unit::PiB),
+        ("ei", "eib", Unit::EiB),
+    ];
+
+    let mut i = 0;
+    while i < SYMBOLS.len() {
+        let (prefix, symbol, unit) = SYMBOLS[i];
+        if input.eq_ignore_ascii_case(prefix) || input.eq_ignore_ascii_case(symbol) {
+            return Some(unit);
+        }
+        i += 1;
     }
+    None
 }
 
-fn parse_unit(input: &str) -> Result<u64, Error> {
-    match input.to_lowercase().as_str() {
-        "b" => Ok(B),
+/// Case-sensitive [`parse_unit`], where `b` is bit and `m` is milli.
+fn parse_unit_exact(input: &str) -> Option<Unit> {
+    match input {
+        "B" => Some(Unit::B),
         // SI
-        "k" | "kb" => Ok(KB),
-        "m" | "mb" => Ok(MB),
-        "g" | "gb" => Ok(GB),
-        "t" | "tb" => Ok(TB),
-        "p" | "pb" => Ok(PB),
-        "e" | "eb" => Ok(EB),
+        "k" | "kB" => Some(Unit::KB),
+        "M" | "MB" => Some(Unit::MB),
+        "G" | "GB" => Some(Unit::GB),
+        "T" | "TB" => Some(Unit::TB),
+        "P" | "PB" => Some(Unit::PB),
+        "E" | "EB" => Some(Unit::EB),
         // IEC 60027-2
-        "ki" | "kib" => Ok(KIB),
-        "mi" | "mib" => Ok(MIB),
-        "gi" | "gib" => Ok(GIB),
-        "ti" | "tib" => Ok(TIB),
-        "pi" | "pib" => Ok(PIB),
-        "ei" | "eib" => Ok(EIB),
-        _ => Err(Error::Unit),
+        "Ki" | "KiB" => Some(Unit::KiB),
+        "Mi" | "MiB" => Some(Unit::MiB),
+        "Gi" | "GiB" => Some(Unit::GiB),
+        "Ti" | "TiB" => Some(Unit::TiB),
+        "Pi" | "PiB" => Some(Unit::PiB),
+        "Ei" | "EiB" => Some(Unit::EiB),
+        _ => None,
+    }
+}
+
+/// Byte unit of either prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    B,
+    // SI
+    KB,
+    MB,
+    GB,
+    TB,
+    PB,
+    EB,
+    // IEC 60027-2
+    KiB,
+    MiB,
+    GiB,
+    TiB,
+    PiB,
+    EiB,
+}
+
+impl Unit {
+    /// All units, bytes first, then SI and IEC ones in ascending order
+    pub const ALL: [Self; 13] = [
+        Self::B,
+        Self::KB,
+        Self::MB,
+        Self::GB,
+        Self::TB,
+        Self::PB,
+        Self::EB,
+        Self::KiB,
+        Self::MiB,
+        Self::GiB,
+        Self::TiB,
+        Self::PiB,
+        Self::EiB,
+    ];
+
+    /// Number of bytes in the unit
+    #[inline]
+    pub const fn bytes(self) -> u64 {
+        match self {
+            Self::B => B,
+            Self::KB => KB,
+            Self::MB => MB,
+            Self::GB => GB,
+            Self::TB => TB,
+            Self::PB => PB,
+            Self::EB => EB,
+            Self::KiB => KIB,
+            Self::MiB => MIB,
+            Self::GiB => GIB,
+            Self::TiB => TIB,
+            Self::PiB => PIB,
+            Self::EiB => EIB,
+        }
+    }
+
+    /// Whether the unit is byte or of decimal prefix
+    #[inline]
+    pub const fn is_decimal(self) -> bool {
+        matches!(
+            self,
+            Self::B | Self::KB | Self::MB | Self::GB | Self::TB | Self::PB | Self::EB
+        )
+    }
+
+    /// Whether the unit is byte or of binary prefix
+    #[inline]
+    pub const fn is_binary(self) -> bool {
+        matches!(
+            self,
+            Self::B | Self::KiB | Self::MiB | Self::GiB | Self::TiB | Self::PiB | Self::EiB
+        )
+    }
+
+    /// Symbol of the unit as formatted by bytesize types
+    #[inline]
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::B => "B",
+            Self::KB => "kB",
+            Self::MB => "MB",
+            Self::GB => "GB",
+            Self::TB => "TB",
+            Self::PB => "PB",
+            Self::EB => "EB",
+            Self::KiB => "KiB",
+            Self::MiB => "MiB",
+            Self::GiB => "GiB",
+            Self::TiB => "TiB",
+            Self::PiB => "PiB",
+            Self::EiB => "EiB",
+        }
+    }
+}
+
+impl core::fmt::Display for Unit {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+impl FromStr for Unit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_unit(s).ok_or_else(|| {
+            Error::new(ErrorKind::UnknownUnit, 0..s.len(), s)
+                .with_suggestion(suggest_unit(s, Unit::ALL.map(Unit::symbol)))
+        })
     }
 }
 
@@ -456,37 +1327,44 @@ impl From<ByteSizeSi> for ByteSizeIec {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum Error {
-    Empty,
-    Invalid,
-    Unit,
+impl From<ByteSizeJedec> for ByteSizeSi {
+    fn from(jedec: ByteSizeJedec) -> Self {
+        jedec.si()
+    }
 }
 
-impl core::error::Error for Error {}
+impl From<ByteSizeSi> for ByteSizeJedec {
+    fn from(si: ByteSizeSi) -> Self {
+        si.jedec()
+    }
+}
 
-impl core::fmt::Display for Error {
-    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        let msg = match self {
-            Self::Empty => "cannot parse bytesize from empty string",
-            Self::Invalid => "invalid number found in string",
-            Self::Unit => "cannot recognize byte unit in string",
-        };
-        f.write_str(msg)
+impl From<ByteSizeJedec> for ByteSizeIec {
+    fn from(jedec: ByteSizeJedec) -> Self {
+        jedec.iec()
     }
 }
 
-impl From<ParseIntError> for Error {
-    fn from(e: ParseIntError) -> Self {
-        match e.kind() {
-            IntErrorKind::Empty => Self::Empty,
-            _ => Self::Invalid,
-        }
+impl From<ByteSizeIec> for ByteSizeJedec {
+    fn from(iec: ByteSizeIec) -> Self {
+        iec.jedec()
+    }
+}
+
+impl From<ByteSizeSi> for ByteSize {
+    fn from(si: ByteSizeSi) -> Self {
+        si.in_system(System::Si)
+    }
+}
+
+impl From<ByteSizeIec> for ByteSize {
+    fn from(iec: ByteSizeIec) -> Self {
+        iec.in_system(System::Iec)
     }
 }
 
-impl From<ParseFloatError> for Error {
-    fn from(_: ParseFloatError) -> Self {
-        Self::Invalid
+impl From<ByteSizeJedec> for ByteSize {
+    fn from(jedec: ByteSizeJedec) -> Self {
+        jedec.in_system(System::Jedec)
     }
 }
@@ -0,0 +1,88 @@
+use core::{cmp::Ordering, str::FromStr};
+
+use crate::{ByteSizeIec, Error, parse_byte_size};
+
+/// Bytesize limit which may be unlimited, e.g. systemd's `MemoryMax=infinity`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ByteLimit {
+    Limited(ByteSizeIec),
+    Unlimited,
+}
+
+impl ByteLimit {
+    /// Keywords accepted as [`ByteLimit::Unlimited`] by [`FromStr`].
+    pub const KEYWORDS: &'static [&'static str] = &["unlimited", "infinity", "max", "none"];
+
+    /// Parse a limit, accepting the given keywords (case-insensitive) as unlimited.
+    pub fn parse_with_keywords(input: &str, keywords: &[&str]) -> Result<Self, Error> {
+        if keywords.iter().any(|kw| kw.eq_ignore_ascii_case(input)) {
+            Ok(Self::Unlimited)
+        } else {
+            parse_byte_size(input).map(|n| Self::Limited(ByteSizeIec(n)))
+        }
+    }
+
+    #[inline]
+    pub const fn is_unlimited(&self) -> bool {
+        matches!(self, Self::Unlimited)
+    }
+
+    /// The size of the limit, or `None` if unlimited.
+    #[inline]
+    pub const fn size(&self) -> Option<ByteSizeIec> {
+        match *self {
+            Self::Limited(size) => Some(size),
+            Self::Unlimited => None,
+        }
+    }
+
+    /// Whether `size` stays within the limit.
+    #[inline]
+    pub fn allows(&self, size: ByteSizeIec) -> bool {
+        match *self {
+            Self::Limited(limit) => size <= limit,
+            Self::Unlimited => true,
+        }
+    }
+}
+
+impl From<ByteSizeIec> for ByteLimit {
+    fn from(size: ByteSizeIec) -> Self {
+        Self::Limited(size)
+    }
+}
+
+impl From<Option<ByteSizeIec>> for ByteLimit {
+    fn from(size: Option<ByteSizeIec>) -> Self {
+        size.map_or(Self::Unlimited, Self::Limited)
+    }
+}
+
+impl PartialEq<ByteSizeIec> for ByteLimit {
+    fn eq(&self, other: &ByteSizeIec) -> bool {
+        *self == Self::Limited(*other)
+    }
+}
+
+impl PartialOrd<ByteSizeIec> for ByteLimit {
+    fn partial_cmp(&self, other: &ByteSizeIec) -> Option<Ordering> {
+        Some(self.cmp(&Self::Limited(*other)))
+    }
+}
+
+impl core::fmt::Display for ByteLimit {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::Limited(size) => write!(f, "{size}"),
+            Self::Unlimited => f.write_str("unlimited"),
+        }
+    }
+}
+
+impl FromStr for ByteLimit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_keywords(s, Self::KEYWORDS)
+    }
+}
@@ -0,0 +1,31 @@
+/// Bytesize literal parsed at compile time
+///
+/// It's [`ByteSizeIec`](type@crate::ByteSizeIec) unless another bytesize type is given:
+///
+/// ```
+/// use typed_bytesize::{ByteSizeIec, ByteSizeJedec, ByteSizeSi, bytesize};
+///
+/// const MAX_BODY: ByteSizeIec = bytesize!("16MiB");
+/// assert_eq!(MAX_BODY, ByteSizeIec::mib(16));
+/// assert_eq!(bytesize!(ByteSizeSi, "1.5 GB"), ByteSizeSi::mb(1500));
+/// assert_eq!(bytesize!(ByteSizeJedec, "4K"), ByteSizeJedec::kb(4));
+/// ```
+///
+/// Invalid or overflowing literals fail compilation:
+///
+/// ```compile_fail
+/// let _ = typed_bytesize::bytesize!("16 MiBs");
+/// ```
+///
+/// ```compile_fail
+/// let _ = typed_bytesize::bytesize!("17EiB");
+/// ```
+#[macro_export]
+macro_rules! bytesize {
+    ($s:expr) => {
+        $crate::bytesize!($crate::ByteSizeIec, $s)
+    };
+    ($ty:ty, $s:expr) => {
+        const { <$ty>::parse_const($s) }
+    };
+}
@@ -0,0 +1,65 @@
+//! [nom](https://crates.io/crates/nom) parsers of bytesize
+//!
+//! They accept the same grammar as [`FromStr`](core::str::FromStr), and fail with
+//! [`Error`] as the external error, so the [`ErrorKind`](crate::ErrorKind) is kept
+//! by error types like `VerboseError` which record it.
+//!
+//! ```
+//! use nom::{IResult, Parser, bytes::complete::tag, character::complete::space1};
+//! use typed_bytesize::{ByteSizeIec, nom::byte_size};
+//!
+//! fn rule(input: &str) -> IResult<&str, ByteSizeIec> {
+//!     let (input, (_, _, size)) = (tag("if size >"), space1, byte_size).parse(input)?;
+//!     Ok((input, size))
+//! }
+//!
+//! assert_eq!(rule("if size > 10MiB then"), Ok((" then", ByteSizeIec::mib(10))));
+//! ```
+
+use nom::{
+    IResult,
+    error::{ErrorKind, FromExternalError, ParseError},
+};
+
+use crate::{ByteSize, Error, ParseOptions, Unit, UnitSystem};
+
+/// Parse a number like `1` or `1.5`.
+pub fn number<'a, E>(input: &'a str) -> IResult<&'a str, &'a str, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, Error>,
+{
+    let len = crate::parse_number_prefix(input).map_err(|e| failure(input, e))?;
+    Ok((&input[len..], &input[..len]))
+}
+
+/// Parse a unit symbol like `MiB` or `k`, in any case.
+pub fn unit<'a, E>(input: &'a str) -> IResult<&'a str, Unit, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, Error>,
+{
+    let (unit, len) = crate::parse_unit_prefix(input).map_err(|e| failure(input, e))?;
+    Ok((&input[len..], unit))
+}
+
+/// Parse bytesize like `1.5 GiB`, whose unit is the whole word following the number.
+///
+/// The size is in bytes if the word isn't a unit, as [`ByteSize::parse_prefix`] does.
+pub fn byte_size<'a, S, E>(input: &'a str) -> IResult<&'a str, ByteSize<S>, E>
+where
+    S: UnitSystem + Default,
+    E: ParseError<&'a str> + FromExternalError<&'a str, Error>,
+{
+    let system = S::default();
+    let (n, len) = crate::parse_prefix_with(input, &ParseOptions::new(), &system)
+        .map_err(|e| failure(input, e))?;
+    Ok((&input[len..], ByteSize(n, system)))
+}
+
+/// Recoverable error at the offending part of `input`
+fn failure<'a, E>(input: &'a str, error: Error) -> nom::Err<E>
+where
+    E: FromExternalError<&'a str, Error>,
+{
+    let part = &input[error.span().start..];
+    nom::Err::Error(E::from_external_error(part, ErrorKind::MapRes, error))
+}
@@ -0,0 +1,91 @@
+use crate::Unit;
+
+/// Options of bytesize parsing
+///
+/// The default options accept everything [`FromStr`](core::str::FromStr) does,
+/// while [`ParseOptions::strict`] only accepts the exact unit symbols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    pub(crate) default_unit: Option<Unit>,
+    pub(crate) case_sensitive: bool,
+    pub(crate) bare_prefixes: bool,
+    pub(crate) foreign_units: bool,
+    pub(crate) lowercase_b: bool,
+    pub(crate) exact: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseOptions {
+    /// Lenient options, same as [`FromStr`](core::str::FromStr).
+    pub const fn new() -> Self {
+        Self {
+            default_unit: None,
+            case_sensitive: false,
+            bare_prefixes: true,
+            foreign_units: true,
+            lowercase_b: true,
+            exact: false,
+        }
+    }
+
+    /// Strict options, accepting only exact symbols like `kB` or `MiB`
+    /// of the bytesize type's own prefix.
+    pub const fn strict() -> Self {
+        Self {
+            default_unit: None,
+            case_sensitive: true,
+            bare_prefixes: false,
+            foreign_units: false,
+            lowercase_b: false,
+            exact: true,
+        }
+    }
+
+    /// Unit of bare numbers, which are bytes by default.
+    pub const fn default_unit(mut self, unit: Option<Unit>) -> Self {
+        self.default_unit = unit;
+        self
+    }
+
+    /// Whether unit symbols must match in case, e.g. `Mb` isn't `MB`.
+    pub const fn case_sensitive(mut self, yes: bool) -> Self {
+        self.case_sensitive = yes;
+        self
+    }
+
+    /// Whether units made of a bare prefix, e.g. `k`, `M` or `Gi`, are accepted.
+    pub const fn bare_prefixes(mut self, yes: bool) -> Self {
+        self.bare_prefixes = yes;
+        self
+    }
+
+    /// Whether SI units are accepted by [`ByteSizeIec`](type@crate::ByteSizeIec)
+    /// and IEC units by [`ByteSizeSi`](type@crate::ByteSizeSi).
+    pub const fn foreign_units(mut self, yes: bool) -> Self {
+        self.foreign_units = yes;
+        self
+    }
+
+    /// Whether a lowercase `b`, which may mean bits, is accepted as bytes.
+    ///
+    /// Otherwise such units are rejected with [`ErrorKind::AmbiguousUnit`](crate::ErrorKind::AmbiguousUnit).
+    pub const fn lowercase_b(mut self, yes: bool) -> Self {
+        self.lowercase_b = yes;
+        self
+    }
+
+    /// Whether the size must be a whole number of bytes within range,
+    /// rather than being truncated or saturated.
+    ///
+    /// Otherwise such sizes are rejected with [`ErrorKind::FractionalBytes`](crate::ErrorKind::FractionalBytes)
+    /// or [`ErrorKind::Overflow`](crate::ErrorKind::Overflow).
+    pub const fn exact(mut self, yes: bool) -> Self {
+        self.exact = yes;
+        self
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{Error, Unit, parse_byte_size, parse_unit};
+
+/// Bytesize which remembers how it was written, for lossless rewriting
+///
+/// It's displayed as the original input until the value changes, and then in the
+/// original unit and spacing as long as the value is exact in a few digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParsedByteSize<'a> {
+    bytes: u64,
+    original: u64,
+    number: &'a str,
+    spacing: &'a str,
+    symbol: &'a str,
+}
+
+impl<'a> ParsedByteSize<'a> {
+    /// Fraction digits tried when re-emitting a changed value in the original unit
+    const FRACTION_DIGITS: u32 = 3;
+
+    /// Parse bytesize as [`FromStr`](core::str::FromStr) of bytesize types do,
+    /// keeping its text.
+    pub fn parse(input: &'a str) -> Result<Self, Error> {
+        let bytes = parse_byte_size(input)?;
+
+        // The input is known to be `decimal " "* unit?`
+        let number_len = input
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(input.len());
+        let (number, rest) = input.split_at(number_len);
+        let symbol = rest.trim_start_matches(' ');
+        let spacing = &rest[..rest.len() - symbol.len()];
+
+        Ok(Self {
+            bytes,
+            original: bytes,
+            number,
+            spacing,
+            symbol,
+        })
+    }
+
+    #[inline]
+    pub const fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Change the value, after which it's no longer displayed as the original input.
+    #[inline]
+    pub const fn set_bytes(&mut self, bytes: u64) {
+        self.bytes = bytes;
+    }
+
+    /// Whether the value differs from the parsed one
+    #[inline]
+    pub const fn is_changed(&self) -> bool {
+        self.bytes != self.original
+    }
+
+    /// Original number text, e.g. `1.5` of `1.5 GiB`
+    #[inline]
+    pub const fn number(&self) -> &'a str {
+        self.number
+    }
+
+    /// Original spaces between the number and the unit
+    #[inline]
+    pub const fn spacing(&self) -> &'a str {
+        self.spacing
+    }
+
+    /// Original unit text, e.g. `gib` of `1gib`, which is empty for bare numbers
+    #[inline]
+    pub const fn symbol(&self) -> &'a str {
+        self.symbol
+    }
+
+    /// Original unit, which is `None` for bare numbers
+    #[inline]
+    pub fn unit(&self) -> Option<Unit> {
+        parse_unit(self.symbol)
+    }
+}
+
+impl core::fmt::Display for ParsedByteSize<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if !self.is_changed() {
+            return write!(f, "{}{}{}", self.number, self.spacing, self.symbol);
+        }
+
+        let Some(unit) = self.unit() else {
+            return write!(f, "{}", self.bytes);
+        };
+
+        let (integer, remainder) = (self.bytes / unit.bytes(), self.bytes % unit.bytes());
+        if remainder == 0 {
+            return write!(f, "{integer}{}{}", self.spacing, self.symbol);
+        }
+
+        for digits in 1..=Self::FRACTION_DIGITS {
+            let scaled = remainder as u128 * 10u128.pow(digits);
+            if scaled.is_multiple_of(unit.bytes() as u128) {
+                let fraction = scaled / unit.bytes() as u128;
+                return write!(
+                    f,
+                    "{integer}.{fraction:0digits$}{}{}",
+                    self.spacing,
+                    self.symbol,
+                    digits = digits as usize,
+                );
+            }
+        }
+
+        // Inexact in the original unit
+        write!(f, "{}{}B", self.bytes, self.spacing)
+    }
+}
+
+impl From<ParsedByteSize<'_>> for u64 {
+    fn from(parsed: ParsedByteSize<'_>) -> Self {
+        parsed.bytes
+    }
+}
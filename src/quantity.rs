@@ -0,0 +1,260 @@
+//! Quantities of compile-time units, e.g. `Quantity<MiB>`
+//!
+//! ```
+//! use typed_bytesize::{ByteSizeIec, ConversionError, quantity::{GiB, MiB, Quantity}};
+//!
+//! fn set_cache(size: Quantity<MiB>) -> Result<ByteSizeIec, ConversionError> {
+//!     size.try_into()
+//! }
+//!
+//! let cache = Quantity::<GiB>::new(2).widen::<MiB>().unwrap() + Quantity::new(512);
+//! assert_eq!(set_cache(cache), Ok(ByteSizeIec::mib(2560)));
+//! ```
+//!
+//! Arithmetic only compiles between quantities of the same unit:
+//!
+//! ```compile_fail
+//! use typed_bytesize::quantity::{GiB, MiB, Quantity};
+//!
+//! let _ = Quantity::<GiB>::new(2) + Quantity::<MiB>::new(512);
+//! ```
+//!
+//! and widening only into units dividing the source:
+//!
+//! ```compile_fail
+//! use typed_bytesize::quantity::{GB, GiB, Quantity};
+//!
+//! let _ = Quantity::<GiB>::new(2).widen::<GB>();
+//! ```
+
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
+
+use crate::{ByteSize, ConversionError, ErrorKind, Unit, UnitSystem};
+
+/// Byte unit known at compile time
+pub trait StaticUnit {
+    const UNIT: Unit;
+}
+
+macro_rules! static_units {
+    ($($name:ident),+) => {
+        $(
+            #[doc = concat!("`", stringify!($name), "` as a type")]
+            #[derive(Debug)]
+            pub enum $name {}
+
+            impl StaticUnit for $name {
+                const UNIT: Unit = Unit::$name;
+            }
+        )+
+    };
+}
+
+static_units!(B, KB, MB, GB, TB, PB, EB, KiB, MiB, GiB, TiB, PiB, EiB);
+
+/// Amount of unit `U`
+///
+/// Quantities of different units can't be mixed in arithmetic.
+pub struct Quantity<U>(u64, PhantomData<fn() -> U>);
+
+impl<U: StaticUnit> Quantity<U> {
+    #[inline(always)]
+    pub const fn new(n: u64) -> Self {
+        Self(n, PhantomData)
+    }
+
+    /// Amount in `U`
+    #[inline(always)]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Bytes of the quantity, or `None` on overflow.
+    #[inline]
+    pub const fn checked_bytes(self) -> Option<u64> {
+        self.0.checked_mul(U::UNIT.bytes())
+    }
+
+    /// Quantity of `bytes`, or `None` if it isn't a whole amount of `U`.
+    #[inline]
+    pub const fn from_bytes(bytes: u64) -> Option<Self> {
+        match bytes.is_multiple_of(U::UNIT.bytes()) {
+            true => Some(Self::new(bytes / U::UNIT.bytes())),
+            false => None,
+        }
+    }
+
+    /// Convert into a smaller unit `V` which divides `U`, e.g. GiB into MiB,
+    /// or `None` on overflow.
+    ///
+    /// Units which don't divide `U` are rejected at compile time.
+    #[inline]
+    pub const fn widen<V: StaticUnit>(self) -> Option<Quantity<V>> {
+        const {
+            assert!(
+                U::UNIT.bytes().is_multiple_of(V::UNIT.bytes()),
+                "the target unit must divide the source unit"
+            )
+        };
+        match self.0.checked_mul(U::UNIT.bytes() / V::UNIT.bytes()) {
+            Some(n) => Some(Quantity::new(n)),
+            None => None,
+        }
+    }
+
+    /// Convert into any unit `V`, or `None` if inexact or overflowing.
+    #[inline]
+    pub const fn convert<V: StaticUnit>(self) -> Option<Quantity<V>> {
+        match self.checked_bytes() {
+            Some(bytes) => Quantity::from_bytes(bytes),
+            None => None,
+        }
+    }
+}
+
+impl<U> Clone for Quantity<U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U> Copy for Quantity<U> {}
+
+impl<U> Default for Quantity<U> {
+    #[inline]
+    fn default() -> Self {
+        Self(0, PhantomData)
+    }
+}
+
+impl<U> PartialEq for Quantity<U> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<U> Eq for Quantity<U> {}
+
+impl<U> PartialOrd for Quantity<U> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<U> Ord for Quantity<U> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<U> Hash for Quantity<U> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<U: StaticUnit> core::fmt::Debug for Quantity<U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Quantity({}{})", self.0, U::UNIT)
+    }
+}
+
+impl<U: StaticUnit> core::fmt::Display for Quantity<U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}{}", self.0, U::UNIT)
+    }
+}
+
+impl<U: StaticUnit, S: UnitSystem + Default> TryFrom<Quantity<U>> for ByteSize<S> {
+    type Error = ConversionError;
+
+    #[inline]
+    fn try_from(quantity: Quantity<U>) -> Result<Self, Self::Error> {
+        quantity
+            .checked_bytes()
+            .map(Self::b)
+            .ok_or(ConversionError::new(ErrorKind::Overflow))
+    }
+}
+
+impl<U> Add for Quantity<U> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, PhantomData)
+    }
+}
+
+impl<U> AddAssign for Quantity<U> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl<U> Sub for Quantity<U> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, PhantomData)
+    }
+}
+
+impl<U> SubAssign for Quantity<U> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+macro_rules! impl_scalar {
+    ($($ty:ty),+) => {
+        $(
+            impl<U> Mul<$ty> for Quantity<U> {
+                type Output = Self;
+
+                #[inline]
+                fn mul(self, rhs: $ty) -> Self::Output {
+                    Self(self.0 * rhs as u64, PhantomData)
+                }
+            }
+
+            impl<U> MulAssign<$ty> for Quantity<U> {
+                #[inline]
+                fn mul_assign(&mut self, rhs: $ty) {
+                    self.0 *= rhs as u64;
+                }
+            }
+
+            impl<U> Div<$ty> for Quantity<U> {
+                type Output = Self;
+
+                #[inline]
+                fn div(self, rhs: $ty) -> Self::Output {
+                    Self(self.0 / rhs as u64, PhantomData)
+                }
+            }
+
+            impl<U> DivAssign<$ty> for Quantity<U> {
+                #[inline]
+                fn div_assign(&mut self, rhs: $ty) {
+                    self.0 /= rhs as u64;
+                }
+            }
+        )+
+    };
+}
+
+impl_scalar!(u8, u16, u32, u64, usize);
@@ -0,0 +1,213 @@
+use core::str::FromStr;
+
+use crate::{ByteSizeIec, Error, ErrorKind, parse_byte_size};
+
+/// Bytesize which may be relative to a total, e.g. `25%` of RAM
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelativeByteSize {
+    /// An absolute bytesize, e.g. `512MiB`
+    Absolute(ByteSizeIec),
+    /// A percentage of the total, e.g. `25%`
+    Percent { percent: f64, clamp: Clamp },
+    /// A fraction of the total, e.g. `1/4`
+    Fraction {
+        numerator: u64,
+        denominator: u64,
+        clamp: Clamp,
+    },
+}
+
+/// Bounds applied to a resolved relative bytesize
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Clamp {
+    pub min: Option<ByteSizeIec>,
+    pub max: Option<ByteSizeIec>,
+}
+
+impl Clamp {
+    #[inline]
+    pub fn apply(self, size: ByteSizeIec) -> ByteSizeIec {
+        let size = self.min.map_or(size, |min| size.max(min));
+        self.max.map_or(size, |max| size.min(max))
+    }
+}
+
+impl RelativeByteSize {
+    /// Resolve into an absolute bytesize against `total`.
+    pub fn resolve(&self, total: ByteSizeIec) -> ByteSizeIec {
+        match *self {
+            Self::Absolute(size) => size,
+            Self::Percent { percent, clamp } => {
+                clamp.apply(ByteSizeIec((total.0 as f64 * percent / 100.0) as u64))
+            }
+            Self::Fraction {
+                numerator,
+                denominator,
+                clamp,
+            } => {
+                let size = total.0 as u128 * numerator as u128 / denominator as u128;
+                clamp.apply(ByteSizeIec(size.try_into().unwrap_or(u64::MAX)))
+            }
+        }
+    }
+
+    /// Bounds of the relative forms, or `None` for [`Self::Absolute`].
+    #[inline]
+    pub const fn clamp(&self) -> Option<Clamp> {
+        match *self {
+            Self::Absolute(_) => None,
+            Self::Percent { clamp, .. } | Self::Fraction { clamp, .. } => Some(clamp),
+        }
+    }
+}
+
+impl From<ByteSizeIec> for RelativeByteSize {
+    fn from(size: ByteSizeIec) -> Self {
+        Self::Absolute(size)
+    }
+}
+
+impl core::fmt::Display for RelativeByteSize {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.fmt_with(f, |size, f| write!(f, "{size}"))
+    }
+}
+
+impl RelativeByteSize {
+    /// Format as [`Display`](core::fmt::Display) does, writing sizes with `size`.
+    pub(crate) fn fmt_with(
+        &self,
+        f: &mut core::fmt::Formatter,
+        size: impl Fn(ByteSizeIec, &mut core::fmt::Formatter) -> core::fmt::Result,
+    ) -> core::fmt::Result {
+        let clamp = match *self {
+            Self::Absolute(absolute) => return size(absolute, f),
+            Self::Percent { percent, clamp } => {
+                write!(f, "{percent}%")?;
+                clamp
+            }
+            Self::Fraction {
+                numerator,
+                denominator,
+                clamp,
+            } => {
+                write!(f, "{numerator}/{denominator}")?;
+                clamp
+            }
+        };
+
+        if let Some(min) = clamp.min {
+            f.write_str(",min=")?;
+            size(min, f)?;
+        }
+        if let Some(max) = clamp.max {
+            f.write_str(",max=")?;
+            size(max, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RelativeByteSize {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, bounds) = match s.split_once(',') {
+            Some((value, bounds)) => (value.trim(), Some(bounds)),
+            None => (s.trim(), None),
+        };
+
+        let clamp = bounds
+            .map(|bounds| parse_clamp(s, bounds))
+            .transpose()?
+            .unwrap_or_default();
+
+        if let Some(percent) = value.strip_suffix('%') {
+            Ok(Self::Percent {
+                percent: parse_ratio(s, percent.trim_end_matches(' '))?,
+                clamp,
+            })
+        } else if let Some((numerator, denominator)) = value.split_once('/') {
+            let numerator = parse_integer(s, numerator.trim())?;
+            let denominator = denominator.trim();
+            let denominator = match parse_integer(s, denominator)? {
+                0 => return Err(Error::at(ErrorKind::BadNumber, s, denominator)),
+                n => n,
+            };
+            Ok(Self::Fraction {
+                numerator,
+                denominator,
+                clamp,
+            })
+        } else if let Some(bounds) = bounds {
+            // Clamping an absolute bytesize is meaningless
+            Err(Error::at(ErrorKind::Invalid, s, bounds))
+        } else {
+            parse_byte_size(value)
+                .map(|n| Self::Absolute(ByteSizeIec(n)))
+                .map_err(|e| e.within(s, value))
+        }
+    }
+}
+
+/// Parse comma-separated `min=SIZE` and `max=SIZE` bounds.
+fn parse_clamp(input: &str, bounds: &str) -> Result<Clamp, Error> {
+    let mut clamp = Clamp::default();
+
+    for part in bounds.split(',') {
+        let (key, size) = part
+            .split_once('=')
+            .ok_or_else(|| Error::at(ErrorKind::Invalid, input, part))?;
+        let size = size.trim();
+        let size = parse_byte_size(size)
+            .map(ByteSizeIec)
+            .map_err(|e| e.within(input, size))?;
+        let bound = match key.trim() {
+            "min" => &mut clamp.min,
+            "max" => &mut clamp.max,
+            _ => return Err(Error::at(ErrorKind::Invalid, input, key)),
+        };
+        if bound.replace(size).is_some() {
+            return Err(Error::at(ErrorKind::Invalid, input, part));
+        }
+    }
+
+    if let (Some(min), Some(max)) = (clamp.min, clamp.max)
+        && min > max
+    {
+        return Err(Error::at(ErrorKind::Invalid, input, bounds));
+    }
+
+    Ok(clamp)
+}
+
+fn parse_integer(input: &str, number: &str) -> Result<u64, Error> {
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::at(ErrorKind::BadNumber, input, number));
+    }
+    number
+        .parse::<u64>()
+        .map_err(|_| Error::at(ErrorKind::Overflow, input, number))
+}
+
+/// Parse a non-negative decimal, rejecting forms like `inf` or `1e3`.
+fn parse_ratio(input: &str, number: &str) -> Result<f64, Error> {
+    if number.starts_with('-') {
+        return Err(Error::at(ErrorKind::Negative, input, &number[..1]));
+    }
+
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, "0"));
+    if integer.is_empty()
+        || fraction.is_empty()
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(Error::at(ErrorKind::BadNumber, input, number));
+    }
+
+    number
+        .parse::<f64>()
+        .map_err(|_| Error::at(ErrorKind::BadNumber, input, number))
+}
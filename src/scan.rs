@@ -0,0 +1,118 @@
+//! Scanning free text for bytesize, e.g. in logs
+//!
+//! ```
+//! use typed_bytesize::{ByteSizeIec, scan};
+//!
+//! let line = "uploaded 1.2 GiB in 3s (rss 512MB)";
+//! let sizes: Vec<_> = scan::find_sizes(line).collect();
+//! assert_eq!(sizes, [(9..16, ByteSizeIec(1288490188)), (28..33, ByteSizeIec(512_000_000))]);
+//! ```
+//!
+//! To avoid false positives, a size must have a unit ending in `B`, like `kB`,
+//! `MiB` or `B` in any case, and must not be part of a word, an identifier or
+//! a larger number, like `x86MB`, `5MBps`, `1.2.3GB` or `1,024 MB`.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::ops::Range;
+
+use crate::{ByteSizeIec, Iec, ParseOptions, parse_byte_size_with};
+
+/// Find all sizes in `text` with their byte ranges.
+#[inline]
+pub fn find_sizes(text: &str) -> Sizes<'_> {
+    Sizes { text, pos: 0 }
+}
+
+/// Replace all sizes in `text` with what `f` returns for them.
+///
+/// ```
+/// use typed_bytesize::scan;
+///
+/// let report = scan::replace_sizes("rss 1536MiB, cache 2048 KiB", |size| size.si());
+/// assert_eq!(report, "rss 1.6GB, cache 2.1MB");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn replace_sizes<F, T>(text: &str, mut f: F) -> String
+where
+    F: FnMut(ByteSizeIec) -> T,
+    T: core::fmt::Display,
+{
+    use core::fmt::Write;
+
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, size) in find_sizes(text) {
+        out.push_str(&text[last..range.start]);
+        // Writing into `String` never fails
+        let _ = write!(out, "{}", f(size));
+        last = range.end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// Iterator returned by [`find_sizes`]
+#[derive(Debug, Clone)]
+pub struct Sizes<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Iterator for Sizes<'_> {
+    type Item = (Range<usize>, ByteSizeIec);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let options = ParseOptions::new().bare_prefixes(false);
+        let text = self.text;
+        let bytes = text.as_bytes();
+        let run = |start: usize, f: fn(&u8) -> bool| {
+            start + bytes[start..].iter().take_while(|b| f(b)).count()
+        };
+
+        while self.pos < bytes.len() {
+            let start = self.pos;
+            if !bytes[start].is_ascii_digit() {
+                self.pos += 1;
+                continue;
+            }
+
+            let mut number_end = run(start, u8::is_ascii_digit);
+            if bytes.get(number_end) == Some(&b'.')
+                && run(number_end + 1, u8::is_ascii_digit) > number_end + 1
+            {
+                number_end = run(number_end + 1, u8::is_ascii_digit);
+            }
+            self.pos = number_end;
+
+            let unit_start = run(number_end, |b| *b == b' ');
+            let unit_end = run(unit_start, u8::is_ascii_alphabetic);
+            if unit_end == unit_start || !is_boundary(text, start, unit_end) {
+                continue;
+            }
+
+            if let Ok(n) = parse_byte_size_with(&text[start..unit_end], &options, &Iec) {
+                self.pos = unit_end;
+                return Some((start..unit_end, ByteSizeIec(n)));
+            }
+        }
+        None
+    }
+}
+
+/// Whether `text[start..end]` isn't part of a word, an identifier or a larger number
+fn is_boundary(text: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    let mut before = text[..start].chars().rev();
+    let preceded = match before.next() {
+        None => false,
+        Some(c) if is_word(c) || c == '.' => true,
+        // A sign, or a thousands separator
+        Some('-' | '+') => !before.next().is_some_and(is_word),
+        Some(',') => before.next().is_some_and(|c| c.is_ascii_digit()),
+        Some(_) => false,
+    };
+
+    !preceded && !text[end..].chars().next().is_some_and(is_word)
+}
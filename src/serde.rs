@@ -1,258 +1,617 @@
-use core::str::FromStr;
+//! Serde support and field adapters
+
+use core::{marker::PhantomData, str::FromStr};
+
+use serde::de::{Error, Unexpected, Visitor};
+
+use crate::{
+    ByteLimit, ByteSize, ByteSizeIec, Iec, Jedec, RelativeByteSize, Si, System, Unit, UnitSystem,
+};
+
+macro_rules! impl_serde_static {
+    ($($system:ty),+) => {
+        $(
+            impl serde::Serialize for ByteSize<$system> {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    if serializer.is_human_readable() {
+                        serializer.collect_str(self)
+                    } else {
+                        self.0.serialize(serializer)
+                    }
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for ByteSize<$system> {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    if deserializer.is_human_readable() {
+                        deserializer.deserialize_any(ByteSizeVistor(PhantomData))
+                    } else {
+                        deserializer.deserialize_u64(ByteSizeVistor(PhantomData))
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_serde_static!(Si, Iec, Jedec);
+
+/// Visitor of bytes or sizes with units, in the default of system `T`
+struct ByteSizeVistor<T>(PhantomData<T>);
+
+impl<'de, T: UnitSystem + Default> Visitor<'de> for ByteSizeVistor<T> {
+    type Value = ByteSize<T>;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("an unsigned number with byte unit")
+    }
+
+    fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        u64::try_from(v)
+            .map(ByteSize::b)
+            .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+    }
 
-use crate::{ByteSizeIec, ByteSizeSi};
+    fn visit_i16<E>(self, v: i16) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        u64::try_from(v)
+            .map(ByteSize::b)
+            .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+    }
+
+    fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        u64::try_from(v)
+            .map(ByteSize::b)
+            .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+    }
 
-impl serde::Serialize for ByteSizeSi {
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        u64::try_from(v)
+            .map(ByteSize::b)
+            .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &self))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        u64::try_from(v)
+            .map(ByteSize::b)
+            .map_err(|_| Error::invalid_value(Unexpected::Other("negative integer"), &self))
+    }
+
+    fn visit_u8<E>(self, v: u8) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ByteSize::b(v))
+    }
+
+    fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ByteSize::b(v))
+    }
+
+    fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ByteSize::b(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(ByteSize::b(v))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        u64::try_from(v)
+            .map(ByteSize::b)
+            .map_err(|_| Error::invalid_value(Unexpected::Other("overflow integer"), &self))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        ByteSize::from_str(v).map_err(Error::custom)
+    }
+}
+
+impl serde::Serialize for ByteSize {
+    /// Serialize exact bytes along with the system, e.g. `{"bytes":1536,"system":"si"}`.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        if serializer.is_human_readable() {
-            serializer.collect_str(self)
-        } else {
-            self.0.serialize(serializer)
-        }
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ByteSize", 2)?;
+        state.serialize_field("bytes", &self.0)?;
+        state.serialize_field("system", &self.1)?;
+        state.end()
     }
 }
 
-impl<'de> serde::Deserialize<'de> for ByteSizeSi {
+impl<'de> serde::Deserialize<'de> for ByteSize {
+    /// Deserialize what [`Serialize`](serde::Serialize) produces, or in
+    /// human-readable formats also bytes and sizes with units in
+    /// [`System::default()`].
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        use serde::de::{Error, Unexpected, Visitor};
+        use serde::de::{MapAccess, SeqAccess};
+
+        const FIELDS: &[&str] = &["bytes", "system"];
 
-        struct ByteSizeSiVistor;
+        enum Field {
+            Bytes,
+            System,
+        }
+
+        impl<'de> serde::Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct FieldVisitor;
+
+                impl Visitor<'_> for FieldVisitor {
+                    type Value = Field;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut core::fmt::Formatter<'_>,
+                    ) -> core::fmt::Result {
+                        formatter.write_str("`bytes` or `system`")
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        match v {
+                            "bytes" => Ok(Field::Bytes),
+                            "system" => Ok(Field::System),
+                            _ => Err(Error::unknown_field(v, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
 
-        impl<'de> Visitor<'de> for ByteSizeSiVistor {
-            type Value = ByteSizeSi;
+        struct RuntimeVistor;
+
+        impl<'de> Visitor<'de> for RuntimeVistor {
+            type Value = ByteSize;
 
             fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                formatter.write_str("an unsigned number with byte unit")
+                formatter
+                    .write_str("bytes with a unit system, or an unsigned number with byte unit")
             }
 
-            fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E>
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeSi)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+                ByteSizeVistor(PhantomData).visit_i64(v)
             }
 
-            fn visit_i16<E>(self, v: i16) -> Result<Self::Value, E>
+            fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeSi)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+                ByteSizeVistor(PhantomData).visit_i128(v)
             }
 
-            fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E>
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeSi)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+                ByteSizeVistor(PhantomData).visit_u64(v)
             }
 
-            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeSi)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &self))
+                ByteSizeVistor(PhantomData).visit_u128(v)
             }
 
-            fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeSi)
-                    .map_err(|_| Error::invalid_value(Unexpected::Other("negative integer"), &self))
+                ByteSizeVistor(PhantomData).visit_str(v)
             }
 
-            fn visit_u8<E>(self, v: u8) -> Result<Self::Value, E>
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
             where
-                E: Error,
+                A: SeqAccess<'de>,
             {
-                Ok(ByteSizeSi::b(v))
+                let bytes = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(0, &self))?;
+                let system = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(1, &self))?;
+                Ok(ByteSize::new(bytes, system))
             }
 
-            fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E>
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
             where
-                E: Error,
+                A: MapAccess<'de>,
             {
-                Ok(ByteSizeSi::b(v))
+                let mut bytes = None;
+                let mut system = None;
+                while let Some(field) = map.next_key()? {
+                    match field {
+                        Field::Bytes if bytes.is_some() => {
+                            return Err(Error::duplicate_field("bytes"));
+                        }
+                        Field::Bytes => bytes = Some(map.next_value()?),
+                        Field::System if system.is_some() => {
+                            return Err(Error::duplicate_field("system"));
+                        }
+                        Field::System => system = Some(map.next_value()?),
+                    }
+                }
+                let bytes = bytes.ok_or_else(|| Error::missing_field("bytes"))?;
+                let system = system.ok_or_else(|| Error::missing_field("system"))?;
+                Ok(ByteSize::new(bytes, system))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(RuntimeVistor)
+        } else {
+            deserializer.deserialize_struct("ByteSize", FIELDS, RuntimeVistor)
+        }
+    }
+}
+
+impl serde::Serialize for System {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for System {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SystemVistor;
+
+        impl<'de> Visitor<'de> for SystemVistor {
+            type Value = System;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("a unit system of `si`, `iec` or `jedec`")
             }
 
-            fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E>
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                Ok(ByteSizeSi::b(v))
+                System::from_str(v).map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(SystemVistor)
+    }
+}
+
+impl serde::Serialize for RelativeByteSize {
+    /// Serialize with exact sizes, where [`RelativeByteSize::Absolute`] is
+    /// bytes in human-readable formats.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Absolute(size) if serializer.is_human_readable() => {
+                serializer.serialize_u64(size.0)
+            }
+            _ => serializer.collect_str(&ExactRelative(self)),
+        }
+    }
+}
+
+/// Relative bytesize displayed with exact sizes, e.g. `25%,max=1500B`
+struct ExactRelative<'a>(&'a RelativeByteSize);
+
+impl core::fmt::Display for ExactRelative<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.0.fmt_with(f, |size, f| {
+            let unit = [
+                Unit::EiB,
+                Unit::PiB,
+                Unit::TiB,
+                Unit::GiB,
+                Unit::MiB,
+                Unit::KiB,
+            ]
+            .into_iter()
+            .find(|unit| size.0 != 0 && size.0 % unit.bytes() == 0)
+            .unwrap_or(Unit::B);
+            write!(f, "{}{unit}", size.0 / unit.bytes())
+        })
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for RelativeByteSize {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct RelativeByteSizeVistor;
+
+        impl<'de> Visitor<'de> for RelativeByteSizeVistor {
+            type Value = RelativeByteSize;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("a bytesize or a percentage/fraction of the total")
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                Ok(ByteSizeSi(v))
+                Ok(RelativeByteSize::Absolute(ByteSizeIec(v)))
             }
 
-            fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
                 v.try_into()
-                    .map(ByteSizeSi)
-                    .map_err(|_| Error::invalid_value(Unexpected::Other("overflow integer"), &self))
+                    .map(|v| RelativeByteSize::Absolute(ByteSizeIec(v)))
+                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &self))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                ByteSizeSi::from_str(v).map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+                RelativeByteSize::from_str(v).map_err(Error::custom)
             }
         }
 
         if deserializer.is_human_readable() {
-            deserializer.deserialize_any(ByteSizeSiVistor)
+            deserializer.deserialize_any(RelativeByteSizeVistor)
         } else {
-            deserializer.deserialize_u64(ByteSizeSiVistor)
+            deserializer.deserialize_str(RelativeByteSizeVistor)
         }
     }
 }
 
-impl serde::Serialize for ByteSizeIec {
+impl serde::Serialize for ByteLimit {
+    /// Serialize limits as exact bytes, and [`ByteLimit::Unlimited`] as
+    /// `"unlimited"` in human-readable formats or `None` otherwise.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        if serializer.is_human_readable() {
-            serializer.collect_str(self)
-        } else {
-            self.0.serialize(serializer)
+        match self {
+            Self::Limited(size) if serializer.is_human_readable() => {
+                serializer.serialize_u64(size.0)
+            }
+            Self::Unlimited if serializer.is_human_readable() => serializer.collect_str(self),
+            _ => self.size().serialize(serializer),
         }
     }
 }
 
-impl<'de> serde::Deserialize<'de> for ByteSizeIec {
+impl<'de> serde::Deserialize<'de> for ByteLimit {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        use serde::de::{Error, Unexpected, Visitor};
-
-        struct ByteSizeIecVistor;
+        struct ByteLimitVistor;
 
-        impl<'de> Visitor<'de> for ByteSizeIecVistor {
-            type Value = ByteSizeIec;
+        impl<'de> Visitor<'de> for ByteLimitVistor {
+            type Value = ByteLimit;
 
             fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                formatter.write_str("an unsigned number with byte unit")
+                formatter.write_str("an unsigned number with byte unit or an unlimited keyword")
             }
 
-            fn visit_i8<E>(self, v: i8) -> Result<Self::Value, E>
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeIec)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+                Ok(ByteLimit::Limited(ByteSizeIec(v)))
             }
 
-            fn visit_i16<E>(self, v: i16) -> Result<Self::Value, E>
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
                 v.try_into()
-                    .map(ByteSizeIec)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+                    .map(|v| ByteLimit::Limited(ByteSizeIec(v)))
+                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &self))
             }
 
-            fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E>
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeIec)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v as i64), &self))
+                ByteLimit::from_str(v).map_err(Error::custom)
             }
 
-            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            fn visit_none<E>(self) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeIec)
-                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &self))
+                Ok(ByteLimit::Unlimited)
             }
 
-            fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeIec)
-                    .map_err(|_| Error::invalid_value(Unexpected::Other("negative integer"), &self))
+                Ok(ByteLimit::Unlimited)
             }
 
-            fn visit_u8<E>(self, v: u8) -> Result<Self::Value, E>
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
             where
-                E: Error,
+                D: serde::Deserializer<'de>,
             {
-                Ok(ByteSizeIec::b(v))
+                serde::Deserialize::deserialize(deserializer).map(ByteLimit::Limited)
             }
+        }
 
-            fn visit_u16<E>(self, v: u16) -> Result<Self::Value, E>
-            where
-                E: Error,
-            {
-                Ok(ByteSizeIec::b(v))
-            }
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(ByteLimitVistor)
+        } else {
+            deserializer.deserialize_option(ByteLimitVistor)
+        }
+    }
+}
 
-            fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E>
-            where
-                E: Error,
-            {
-                Ok(ByteSizeIec::b(v))
+/// Adapters for `#[serde(with = "...")]` interpreting bare numbers in a default unit
+///
+/// Both integers and unitless strings take the default unit of the adapter,
+/// while explicit units still override it.
+///
+/// ```
+/// use typed_bytesize::ByteSizeIec;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     #[serde(with = "typed_bytesize::serde::default_unit::mib")]
+///     cache: ByteSizeIec,
+/// }
+/// ```
+pub mod default_unit {
+    use crate::{Iec, ParseOptions, Unit, parse_byte_size_with};
+
+    macro_rules! adapter {
+        ($($name:ident => $unit:ident),* $(,)?) => {
+            $(
+                #[doc = concat!("Interpret bare numbers as ", stringify!($unit))]
+                pub mod $name {
+                    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+                    where
+                        S: serde::Serializer,
+                        T: serde::Serialize,
+                    {
+                        value.serialize(serializer)
+                    }
+
+                    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+                    where
+                        D: serde::Deserializer<'de>,
+                        T: From<u64>,
+                    {
+                        super::deserialize(deserializer, super::Unit::$unit)
+                    }
+                }
+            )*
+        };
+    }
+
+    adapter! {
+        b => B,
+        kb => KB,
+        mb => MB,
+        gb => GB,
+        tb => TB,
+        pb => PB,
+        eb => EB,
+        kib => KiB,
+        mib => MiB,
+        gib => GiB,
+        tib => TiB,
+        pib => PiB,
+        eib => EiB,
+    }
+
+    fn deserialize<'de, D, T>(deserializer: D, unit: Unit) -> Result<T, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        T: From<u64>,
+    {
+        use serde::de::{Error, Unexpected, Visitor};
+
+        struct DefaultUnitVisitor(Unit);
+
+        impl<'de> Visitor<'de> for DefaultUnitVisitor {
+            type Value = u64;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(
+                    formatter,
+                    "an unsigned number in {} or with byte unit",
+                    self.0
+                )
             }
 
             fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                Ok(ByteSizeIec(v))
+                v.checked_mul(self.0.bytes())
+                    .ok_or_else(|| Error::invalid_value(Unexpected::Unsigned(v), &self))
             }
 
-            fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                v.try_into()
-                    .map(ByteSizeIec)
-                    .map_err(|_| Error::invalid_value(Unexpected::Other("overflow integer"), &self))
+                u64::try_from(v)
+                    .map_err(|_| Error::invalid_value(Unexpected::Signed(v), &self))
+                    .and_then(|v| self.visit_u64(v))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                ByteSizeIec::from_str(v)
-                    .map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+                parse_byte_size_with(v, &ParseOptions::new().default_unit(Some(self.0)), &Iec)
+                    .map_err(Error::custom)
             }
         }
 
         if deserializer.is_human_readable() {
-            deserializer.deserialize_any(ByteSizeIecVistor)
+            deserializer
+                .deserialize_any(DefaultUnitVisitor(unit))
+                .map(T::from)
         } else {
-            deserializer.deserialize_u64(ByteSizeIecVistor)
+            deserializer
+                .deserialize_u64(DefaultUnitVisitor(unit))
+                .map(T::from)
         }
     }
 }
@@ -261,7 +620,17 @@ impl<'de> serde::Deserialize<'de> for ByteSizeIec {
 mod tests {
     use serde_json::json;
 
-    use crate::{ByteSizeIec, ByteSizeSi};
+    use crate::{
+        ByteLimit, ByteSize, ByteSizeIec, ByteSizeSi, Clamp, MIB, RelativeByteSize, System,
+    };
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Config {
+        #[serde(with = "crate::serde::default_unit::mib")]
+        cache: ByteSizeIec,
+        #[serde(with = "crate::serde::default_unit::kb")]
+        buffer: ByteSizeSi,
+    }
 
     #[test]
     fn test_ser_si() {
@@ -298,4 +667,141 @@ mod tests {
         let v: ByteSizeIec = serde_json::from_value(json!("1.5GiB")).unwrap();
         assert_eq!(v, ByteSizeIec::mib(1536));
     }
+
+    #[test]
+    fn test_serde_relative() {
+        let v: RelativeByteSize = serde_json::from_value(json!("25%,max=8GiB")).unwrap();
+        assert_eq!(
+            v,
+            RelativeByteSize::Percent {
+                percent: 25.0,
+                clamp: Clamp {
+                    min: None,
+                    max: Some(ByteSizeIec::gib(8)),
+                },
+            }
+        );
+        assert_eq!(serde_json::to_value(v).unwrap(), json!("25%,max=8GiB"));
+
+        let v: RelativeByteSize = serde_json::from_value(json!(1024)).unwrap();
+        assert_eq!(v, RelativeByteSize::Absolute(ByteSizeIec::kib(1)));
+
+        let absolute = RelativeByteSize::Absolute(ByteSizeIec(1500));
+        let fraction = RelativeByteSize::Fraction {
+            numerator: 1,
+            denominator: 3,
+            clamp: Clamp {
+                min: Some(ByteSizeIec(1500)),
+                max: Some(ByteSizeIec::gib(8)),
+            },
+        };
+        let v = serde_json::to_value(absolute).unwrap();
+        assert_eq!(v, json!(1500));
+        assert_eq!(
+            serde_json::from_value::<RelativeByteSize>(v).unwrap(),
+            absolute
+        );
+        let v = serde_json::to_value(fraction).unwrap();
+        assert_eq!(v, json!("1/3,min=1500B,max=8GiB"));
+        assert_eq!(
+            serde_json::from_value::<RelativeByteSize>(v).unwrap(),
+            fraction
+        );
+
+        // Non-human-readable
+        for v in [absolute, fraction] {
+            let mut buf = Vec::new();
+            ciborium::into_writer(&v, &mut buf).unwrap();
+            assert_eq!(
+                ciborium::from_reader::<RelativeByteSize, _>(&buf[..]).unwrap(),
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde_limit() {
+        let v = serde_json::to_value(ByteLimit::Unlimited).unwrap();
+        assert_eq!(v, json!("unlimited"));
+        let v: ByteLimit = serde_json::from_value(v).unwrap();
+        assert_eq!(v, ByteLimit::Unlimited);
+
+        let v = serde_json::to_value(ByteLimit::Limited(ByteSizeIec::mib(512))).unwrap();
+        assert_eq!(v, json!(512 * MIB));
+        let v: ByteLimit = serde_json::from_value(v).unwrap();
+        assert_eq!(v, ByteLimit::Limited(ByteSizeIec::mib(512)));
+
+        let v = serde_json::to_value(ByteLimit::Limited(ByteSizeIec(1500))).unwrap();
+        assert_eq!(v, json!(1500));
+        let v: ByteLimit = serde_json::from_value(v).unwrap();
+        assert_eq!(v, ByteLimit::Limited(ByteSizeIec(1500)));
+        let v: ByteLimit = serde_json::from_value(json!("1.5KiB")).unwrap();
+        assert_eq!(v, ByteLimit::Limited(ByteSizeIec(1536)));
+
+        let v: ByteLimit = serde_json::from_value(json!("infinity")).unwrap();
+        assert_eq!(v, ByteLimit::Unlimited);
+        let v: ByteLimit = serde_json::from_value(json!(null)).unwrap();
+        assert_eq!(v, ByteLimit::Unlimited);
+        let v: ByteLimit = serde_json::from_value(json!(4096)).unwrap();
+        assert_eq!(v, ByteLimit::Limited(ByteSizeIec::kib(4)));
+    }
+
+    #[test]
+    fn test_deser_default_unit() {
+        let v: Config = serde_json::from_value(json!({ "cache": 512, "buffer": "4" })).unwrap();
+        assert_eq!(v.cache, ByteSizeIec::mib(512));
+        assert_eq!(v.buffer, ByteSizeSi::kb(4));
+
+        let v: Config = serde_json::from_value(json!({ "cache": "1.5", "buffer": "2MB" })).unwrap();
+        assert_eq!(v.cache, ByteSizeIec::kib(1536));
+        assert_eq!(v.buffer, ByteSizeSi::mb(2));
+
+        assert!(serde_json::from_value::<Config>(json!({ "cache": -1, "buffer": 0 })).is_err());
+        assert!(
+            serde_json::from_value::<Config>(json!({ "cache": u64::MAX, "buffer": 0 })).is_err()
+        );
+    }
+
+    #[test]
+    fn test_serde_system() {
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Settings {
+            display_units: System,
+        }
+
+        let settings: Settings = serde_json::from_value(json!({ "display_units": "SI" })).unwrap();
+        assert_eq!(settings.display_units, System::Si);
+        let v = serde_json::to_value(&settings).unwrap();
+        assert_eq!(v, json!({ "display_units": "si" }));
+
+        assert!(serde_json::from_value::<System>(json!("metric")).is_err());
+        assert!(serde_json::from_value::<System>(json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_serde_runtime() {
+        for system in System::ALL {
+            let size = ByteSize::new(1536, system);
+            let v = serde_json::to_value(size).unwrap();
+            assert_eq!(v, json!({ "bytes": 1536, "system": system.name() }));
+            let v: ByteSize = serde_json::from_value(v).unwrap();
+            assert_eq!(v, size);
+            assert_eq!(v.system(), system);
+
+            let mut buf = Vec::new();
+            ciborium::into_writer(&size, &mut buf).unwrap();
+            let v: ByteSize = ciborium::from_reader(&buf[..]).unwrap();
+            assert_eq!((v, v.system()), (size, system));
+        }
+
+        let v: ByteSize = serde_json::from_value(json!("1.5MB")).unwrap();
+        assert_eq!(v, ByteSize::b(1_500_000u64));
+        assert_eq!(v.system(), System::default());
+        let v: ByteSize = serde_json::from_value(json!(2048)).unwrap();
+        assert_eq!(v, ByteSize::b(2048u64));
+
+        assert!(serde_json::from_value::<ByteSize>(json!({ "bytes": 1 })).is_err());
+        let e = serde_json::from_value::<ByteSize>(json!("1.5 MiBs")).unwrap_err();
+        assert!(e.to_string().contains("did you mean 'MiB'?"), "{e}");
+    }
 }
@@ -0,0 +1,162 @@
+//! Ordering of size strings as GNU `sort -h` does
+//!
+//! Sizes are read at the start of strings after blanks, so columns like
+//! `1.5G  /usr` are ordered by their first field. As `sort -h` does in the C
+//! locale, strings are ordered by the letter right after the number first,
+//! `K` (or `k`) < `M` < `G` < ... < `Q`, and only then by the number. The rest
+//! of a unit is ignored, so `1kB`, `1K` and `1KiB` all weigh the same, and
+//! `2000K` comes before `1M`.
+//!
+//! ```
+//! use typed_bytesize::sort;
+//!
+//! let mut sizes = ["1.5G", "512K", "3.2MiB", "-", "12", "-1K"];
+//! sizes.sort_by(|a, b| sort::cmp_human(a, b));
+//! assert_eq!(sizes, ["-1K", "-", "12", "512K", "3.2MiB", "1.5G"]);
+//! ```
+
+use core::cmp::Ordering;
+
+/// Key of a string in human-readable ordering
+///
+/// As `sort -h` does, keys are ordered by the signed order of the unit
+/// letter, then by the number, and equal ones by the whole string. Strings
+/// which aren't numbers are zero, and so are numbers followed by a blank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortKey<'a> {
+    order: i8,
+    number: Number<'a>,
+    line: &'a str,
+}
+
+impl<'a> SortKey<'a> {
+    /// Order of the unit letter, e.g. `1` for `K` and `-3` for `-2G`
+    ///
+    /// It's zero for zero and numbers without units.
+    #[inline]
+    pub fn order(&self) -> i8 {
+        self.order
+    }
+
+    /// The whole string
+    #[inline]
+    pub fn line(&self) -> &'a str {
+        self.line
+    }
+}
+
+/// Decimal number as text, whose digits are compared without converting them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Number<'a> {
+    /// Whether the number is below zero, which is never for zero
+    negative: bool,
+    /// Integer digits without leading zeros
+    integer: &'a str,
+    /// Fraction digits without trailing zeros
+    fraction: &'a str,
+}
+
+impl Number<'_> {
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        self.integer
+            .len()
+            .cmp(&other.integer.len())
+            .then_with(|| self.integer.cmp(other.integer))
+            .then_with(|| self.fraction.cmp(other.fraction))
+    }
+}
+
+impl Ord for Number<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => other.cmp_magnitude(self),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+}
+
+impl PartialOrd for Number<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Key of `s` in human-readable ordering.
+pub fn sort_key(s: &str) -> SortKey<'_> {
+    let text = s.trim_start_matches([' ', '\t']);
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+
+    let (negative, magnitude) = match text.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, text),
+    };
+
+    let integer = &magnitude[..digits(magnitude)];
+    let rest = &magnitude[integer.len()..];
+    let (fraction, rest) = match rest.strip_prefix('.').map(|rest| (digits(rest), rest)) {
+        Some((len, rest)) if len > 0 => rest.split_at(len),
+        _ => ("", rest),
+    };
+
+    let integer = integer.trim_start_matches('0');
+    let fraction = fraction.trim_end_matches('0');
+    let is_zero = integer.is_empty() && fraction.is_empty();
+
+    let order = match is_zero {
+        true => 0,
+        false => unit_order(rest.bytes().next()),
+    };
+
+    SortKey {
+        order: if negative { -order } else { order },
+        number: Number {
+            negative: negative && !is_zero,
+            integer,
+            fraction,
+        },
+        line: s,
+    }
+}
+
+/// Compare size strings as `sort -h` does.
+#[inline]
+pub fn cmp_human(a: &str, b: &str) -> Ordering {
+    sort_key(a).cmp(&sort_key(b))
+}
+
+/// Sort records stably by the size strings `f` returns for them.
+///
+/// ```
+/// use typed_bytesize::sort;
+///
+/// let mut usage = [("/usr", "1.5G"), ("/etc", "12M"), ("/home", "20G")];
+/// sort::sort_by_size(&mut usage, |(_, size)| size);
+/// assert_eq!(usage.map(|(dir, _)| dir), ["/etc", "/usr", "/home"]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sort_by_size<T, F>(records: &mut [T], mut f: F)
+where
+    F: FnMut(&T) -> &str,
+{
+    records.sort_by(|a, b| cmp_human(f(a), f(b)));
+}
+
+/// Order of the unit letter following a number, where only `k` is lowercase
+fn unit_order(letter: Option<u8>) -> i8 {
+    match letter {
+        Some(b'K' | b'k') => 1,
+        Some(b'M') => 2,
+        Some(b'G') => 3,
+        Some(b'T') => 4,
+        Some(b'P') => 5,
+        Some(b'E') => 6,
+        Some(b'Z') => 7,
+        Some(b'Y') => 8,
+        Some(b'R') => 9,
+        Some(b'Q') => 10,
+        _ => 0,
+    }
+}
@@ -0,0 +1,278 @@
+//! Unit systems of bytesize
+
+use core::str::FromStr;
+
+use crate::{Error, ErrorKind, KB, KIB, Unit};
+
+/// System of byte units, which decides how [`ByteSize`](crate::ByteSize) is formatted and parsed
+///
+/// Downstream crates may define their own systems:
+///
+/// ```
+/// use typed_bytesize::{ByteSize, UnitSystem};
+///
+/// /// Sizes of 512-byte sectors
+/// #[derive(Debug, Clone, Copy, Default)]
+/// struct Sector;
+///
+/// impl UnitSystem for Sector {
+///     fn base(&self) -> u64 {
+///         512
+///     }
+///
+///     fn symbols(&self) -> &'static [&'static str] {
+///         &["s"]
+///     }
+///
+///     fn unit_bytes(&self, symbol: &str) -> Option<u64> {
+///         (symbol == "s").then_some(512)
+///     }
+/// }
+///
+/// let size: ByteSize<Sector> = "8s".parse().unwrap();
+/// assert_eq!(size.0, 4096);
+/// assert_eq!(size.to_string(), "8.0s");
+/// ```
+pub trait UnitSystem: Copy {
+    /// Base between two adjacent units
+    fn base(&self) -> u64;
+
+    /// Unit symbols of ascending powers of [`UnitSystem::base`], from the first power
+    fn symbols(&self) -> &'static [&'static str];
+
+    /// Bytes of a unit symbol specific to the system, which precedes the common units
+    ///
+    /// Under case-sensitive parsing, only [`UnitSystem::symbols`] are accepted here.
+    #[inline]
+    fn unit_bytes(&self, symbol: &str) -> Option<u64> {
+        let _ = symbol;
+        None
+    }
+
+    /// Whether a common unit belongs to the system
+    #[inline]
+    fn owns(&self, unit: Unit) -> bool {
+        unit == Unit::B || self.symbols().contains(&unit.symbol())
+    }
+
+    /// Name of the type alias of [`ByteSize`](crate::ByteSize) in the system, e.g. `ByteSizeIec`
+    ///
+    /// [`Debug`](core::fmt::Debug) prints the alias with the bytes if it exists,
+    /// otherwise `ByteSize` with both the bytes and the system.
+    #[inline]
+    fn alias(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Decimal prefixes of SI, e.g. `kB` and `MB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Si;
+
+impl UnitSystem for Si {
+    #[inline]
+    fn base(&self) -> u64 {
+        KB
+    }
+
+    #[inline]
+    fn symbols(&self) -> &'static [&'static str] {
+        &["kB", "MB", "GB", "TB", "PB", "EB"]
+    }
+
+    #[inline]
+    fn owns(&self, unit: Unit) -> bool {
+        unit.is_decimal()
+    }
+
+    #[inline]
+    fn alias(&self) -> Option<&'static str> {
+        Some("ByteSizeSi")
+    }
+}
+
+/// Binary prefixes of IEC 80000-13, e.g. `KiB` and `MiB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Iec;
+
+impl UnitSystem for Iec {
+    #[inline]
+    fn base(&self) -> u64 {
+        KIB
+    }
+
+    #[inline]
+    fn symbols(&self) -> &'static [&'static str] {
+        &["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"]
+    }
+
+    #[inline]
+    fn owns(&self, unit: Unit) -> bool {
+        unit.is_binary()
+    }
+
+    #[inline]
+    fn alias(&self) -> Option<&'static str> {
+        Some("ByteSizeIec")
+    }
+}
+
+/// Memory notation of JEDEC 100B.01, e.g. `KB` and `MB` of base 1024
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Jedec;
+
+impl UnitSystem for Jedec {
+    #[inline]
+    fn base(&self) -> u64 {
+        KIB
+    }
+
+    #[inline]
+    fn symbols(&self) -> &'static [&'static str] {
+        &["KB", "MB", "GB", "TB", "PB", "EB"]
+    }
+
+    /// `K`, `KB`, `M`, `MB`, ... of base 1024, case-insensitively
+    #[inline]
+    fn unit_bytes(&self, symbol: &str) -> Option<u64> {
+        jedec_unit_bytes(symbol)
+    }
+
+    /// Only `B`, since the common decimal units are of base 1000
+    #[inline]
+    fn owns(&self, unit: Unit) -> bool {
+        unit == Unit::B
+    }
+
+    #[inline]
+    fn alias(&self) -> Option<&'static str> {
+        Some("ByteSizeJedec")
+    }
+}
+
+/// Const [`Jedec::unit_bytes`]
+pub(crate) const fn jedec_unit_bytes(symbol: &str) -> Option<u64> {
+    const PREFIXES: &[u8] = b"KMGTPE";
+
+    let prefix = match symbol.as_bytes() {
+        [prefix] | [prefix, b'B' | b'b'] => prefix.to_ascii_uppercase(),
+        _ => return None,
+    };
+
+    let mut exponent = 0;
+    while exponent < PREFIXES.len() {
+        if PREFIXES[exponent] == prefix {
+            return Some(KIB.pow(exponent as u32 + 1));
+        }
+        exponent += 1;
+    }
+    None
+}
+
+/// Unit system selected at runtime, e.g. from `--units=si`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum System {
+    /// See [`Si`]
+    Si,
+    /// See [`Iec`]
+    #[default]
+    Iec,
+    /// See [`Jedec`]
+    Jedec,
+}
+
+impl System {
+    /// All systems, in the order of declaration
+    pub const ALL: [Self; 3] = [Self::Si, Self::Iec, Self::Jedec];
+
+    /// Lowercase name of the system, as parsed and formatted
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Si => "si",
+            Self::Iec => "iec",
+            Self::Jedec => "jedec",
+        }
+    }
+}
+
+impl UnitSystem for System {
+    #[inline]
+    fn base(&self) -> u64 {
+        match self {
+            Self::Si => Si.base(),
+            Self::Iec => Iec.base(),
+            Self::Jedec => Jedec.base(),
+        }
+    }
+
+    #[inline]
+    fn symbols(&self) -> &'static [&'static str] {
+        match self {
+            Self::Si => Si.symbols(),
+            Self::Iec => Iec.symbols(),
+            Self::Jedec => Jedec.symbols(),
+        }
+    }
+
+    #[inline]
+    fn unit_bytes(&self, symbol: &str) -> Option<u64> {
+        match self {
+            Self::Si => Si.unit_bytes(symbol),
+            Self::Iec => Iec.unit_bytes(symbol),
+            Self::Jedec => Jedec.unit_bytes(symbol),
+        }
+    }
+
+    #[inline]
+    fn owns(&self, unit: Unit) -> bool {
+        match self {
+            Self::Si => Si.owns(unit),
+            Self::Iec => Iec.owns(unit),
+            Self::Jedec => Jedec.owns(unit),
+        }
+    }
+}
+
+impl From<Si> for System {
+    #[inline]
+    fn from(_: Si) -> Self {
+        Self::Si
+    }
+}
+
+impl From<Iec> for System {
+    #[inline]
+    fn from(_: Iec) -> Self {
+        Self::Iec
+    }
+}
+
+impl From<Jedec> for System {
+    #[inline]
+    fn from(_: Jedec) -> Self {
+        Self::Jedec
+    }
+}
+
+impl core::fmt::Display for System {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for System {
+    type Err = Error;
+
+    /// Parse a system name case-insensitively, where `decimal` and `binary`
+    /// are accepted for `si` and `iec`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            _ if s.eq_ignore_ascii_case("si") || s.eq_ignore_ascii_case("decimal") => Ok(Self::Si),
+            _ if s.eq_ignore_ascii_case("iec") || s.eq_ignore_ascii_case("binary") => Ok(Self::Iec),
+            _ if s.eq_ignore_ascii_case("jedec") => Ok(Self::Jedec),
+            _ if s.is_empty() => Err(Error::new(ErrorKind::Empty, 0..0, s)),
+            _ => Err(Error::new(ErrorKind::Invalid, 0..s.len(), s)),
+        }
+    }
+}
@@ -1,6 +1,14 @@
-use core::str::FromStr;
+use core::{cmp::Ordering, str::FromStr};
 
-use crate::{ByteSizeIec, ByteSizeSi, Error, GB, KB, KIB, MIB};
+use crate::{
+    ByteLimit, ByteSize, ByteSizeExt, ByteSizeIec, ByteSizeJedec, ByteSizeSi, Clamp,
+    ConversionError, Dialect, EIB, Error, ErrorKind, GB, GIB, KB, KIB, MB, MIB, ParseOptions,
+    ParsedByteSize, Phrases, RelativeByteSize, Si, System, Unit, UnitSystem, bytesize,
+    dialect::{coreutils::Operation, kubernetes},
+    parse_byte_size_const,
+    quantity::{EiB, GiB, KiB, MB, MiB, Quantity},
+    scan, sort,
+};
 
 macro_rules! assert_si_eq {
     ($s:literal, $bs:expr) => {
@@ -16,16 +24,20 @@ macro_rules! assert_iec_eq {
 
 macro_rules! assert_si_error {
     ($s:literal, $e:expr) => {
-        assert_eq!(ByteSizeSi::from_str($s), Err($e));
+        assert_eq!(kind(ByteSizeSi::from_str($s)), Err($e));
     };
 }
 
 macro_rules! assert_iec_error {
     ($s:literal, $e:expr) => {
-        assert_eq!(ByteSizeIec::from_str($s), Err($e));
+        assert_eq!(kind(ByteSizeIec::from_str($s)), Err($e));
     };
 }
 
+fn kind<T>(result: Result<T, Error>) -> Result<T, ErrorKind> {
+    result.map_err(|e| e.kind())
+}
+
 macro_rules! assert_display {
     ($s:literal, $v:expr) => {
         assert_eq!($s, &$v.to_string());
@@ -66,11 +78,10 @@ fn test_parse_max_si() {
     assert_eq!(size, ByteSizeSi::MAX);
 }
 
-#[cfg(debug_assertions)]
 #[test]
-#[should_panic]
 fn test_parse_oversize_si() {
-    let _ = ByteSizeSi::from_str("19EB");
+    assert_si_error!("19EB", ErrorKind::Overflow);
+    assert_si_error!("18446744073709551616", ErrorKind::Overflow);
 }
 
 #[test]
@@ -118,11 +129,9 @@ fn test_parse_max_iec() {
     assert_eq!(size, ByteSizeIec::MAX);
 }
 
-#[cfg(debug_assertions)]
 #[test]
-#[should_panic]
 fn test_parse_oversize_iec() {
-    let _ = ByteSizeIec::from_str("17EiB");
+    assert_iec_error!("17EiB", ErrorKind::Overflow);
 }
 
 #[test]
@@ -149,7 +158,7 @@ fn test_parse_min() {
 fn test_parse_with_mid_spaces() {
     assert_si_eq!("114.514 kB", ByteSizeSi(114514));
     assert_iec_eq!("114.514    KiB", ByteSizeIec(117262));
-    assert_si_error!("114.514\tKB", Error::Unit);
+    assert_si_error!("114.514\tKB", ErrorKind::BadNumber);
 }
 
 #[test]
@@ -160,12 +169,1499 @@ fn test_cross_parse() {
 
 #[test]
 fn test_parse_error() {
-    assert_iec_error!("114.514", Error::Invalid);
-    assert_si_error!("114.514 ", Error::Unit);
-    assert_si_error!("", Error::Empty);
-    assert_si_error!(".123GB", Error::Invalid);
-    assert_si_error!("2.5E10 B", Error::Unit);
-    assert_iec_error!("11. TiB", Error::Invalid);
-    assert_iec_error!("-9MiB", Error::Invalid);
-    assert_si_error!("inf B", Error::Invalid);
+    assert_iec_error!("114.514", ErrorKind::FractionalBytes);
+    assert_si_error!("114.514 ", ErrorKind::TrailingInput);
+    assert_si_error!("1.5.3GB", ErrorKind::BadNumber);
+    assert_si_error!("", ErrorKind::Empty);
+    assert_si_error!(".123GB", ErrorKind::BadNumber);
+    assert_si_error!("2.5E10 B", ErrorKind::TrailingInput);
+    assert_iec_error!("11. TiB", ErrorKind::BadNumber);
+    assert_iec_error!("-9MiB", ErrorKind::Negative);
+    assert_si_error!("inf B", ErrorKind::BadNumber);
+}
+
+#[test]
+fn test_parse_relative() {
+    assert_eq!(
+        RelativeByteSize::from_str("512MiB"),
+        Ok(RelativeByteSize::Absolute(ByteSizeIec::mib(512)))
+    );
+    assert_eq!(
+        RelativeByteSize::from_str("25%"),
+        Ok(RelativeByteSize::Percent {
+            percent: 25.0,
+            clamp: Clamp::default(),
+        })
+    );
+    assert_eq!(
+        RelativeByteSize::from_str("1/4, min=1GiB"),
+        Ok(RelativeByteSize::Fraction {
+            numerator: 1,
+            denominator: 4,
+            clamp: Clamp {
+                min: Some(ByteSizeIec::gib(1)),
+                max: None,
+            },
+        })
+    );
+    assert_eq!(
+        kind(RelativeByteSize::from_str("512MiB,max=1GiB")),
+        Err(ErrorKind::Invalid)
+    );
+    assert_eq!(
+        kind(RelativeByteSize::from_str("25%,min=8GiB,max=1GiB")),
+        Err(ErrorKind::Invalid)
+    );
+    assert_eq!(
+        kind(RelativeByteSize::from_str("1/0")),
+        Err(ErrorKind::BadNumber)
+    );
+    assert_eq!(
+        kind(RelativeByteSize::from_str("-5%")),
+        Err(ErrorKind::Negative)
+    );
+    assert_eq!(
+        kind(RelativeByteSize::from_str("inf%")),
+        Err(ErrorKind::BadNumber)
+    );
+}
+
+#[test]
+fn test_resolve_relative() {
+    let total = ByteSizeIec::gib(16);
+
+    let size = RelativeByteSize::from_str("25%").unwrap();
+    assert_eq!(size.resolve(total), ByteSizeIec::gib(4));
+
+    let size = RelativeByteSize::from_str("1/3").unwrap();
+    assert_eq!(size.resolve(ByteSizeIec(10)), ByteSizeIec(3));
+
+    let size = RelativeByteSize::from_str("25%,min=1GiB,max=8GiB").unwrap();
+    assert_eq!(size.resolve(ByteSizeIec::gib(2)), ByteSizeIec::gib(1));
+    assert_eq!(size.resolve(ByteSizeIec::gib(64)), ByteSizeIec::gib(8));
+    assert_eq!(size.resolve(total), ByteSizeIec::gib(4));
+
+    let size = RelativeByteSize::Absolute(ByteSizeIec::mib(512));
+    assert_eq!(size.resolve(total), ByteSizeIec::mib(512));
+}
+
+#[test]
+fn test_parse_limit() {
+    assert_eq!(ByteLimit::from_str("unlimited"), Ok(ByteLimit::Unlimited));
+    assert_eq!(ByteLimit::from_str("Infinity"), Ok(ByteLimit::Unlimited));
+    assert_eq!(ByteLimit::from_str("max"), Ok(ByteLimit::Unlimited));
+    assert_eq!(ByteLimit::from_str("none"), Ok(ByteLimit::Unlimited));
+    assert_eq!(
+        ByteLimit::from_str("2GiB"),
+        Ok(ByteLimit::Limited(ByteSizeIec::gib(2)))
+    );
+    assert_eq!(
+        kind(ByteLimit::from_str("never")),
+        Err(ErrorKind::BadNumber)
+    );
+
+    assert_eq!(
+        ByteLimit::parse_with_keywords("max", &["max"]),
+        Ok(ByteLimit::Unlimited)
+    );
+    assert_eq!(
+        kind(ByteLimit::parse_with_keywords("infinity", &["max"])),
+        Err(ErrorKind::BadNumber)
+    );
+}
+
+#[test]
+fn test_cmp_limit() {
+    let limit = ByteLimit::Limited(ByteSizeIec::gib(1));
+
+    assert!(limit < ByteLimit::Unlimited);
+    assert!(limit > ByteSizeIec::mib(512));
+    assert!(limit < ByteSizeIec::gib(2));
+    assert!(ByteLimit::Unlimited > ByteSizeIec::MAX);
+    assert_eq!(limit, ByteSizeIec::gib(1));
+
+    assert!(limit.allows(ByteSizeIec::gib(1)));
+    assert!(!limit.allows(ByteSizeIec::gib(1) + ByteSizeIec(1)));
+    assert!(ByteLimit::Unlimited.allows(ByteSizeIec::MAX));
+
+    assert_eq!(limit.min(ByteLimit::Unlimited), limit);
+    assert_eq!(
+        limit.min(ByteLimit::Limited(ByteSizeIec::mib(1))),
+        ByteSizeIec::mib(1)
+    );
+}
+
+#[test]
+fn test_display_limit() {
+    assert_display!("unlimited", ByteLimit::Unlimited);
+    assert_display!("1.0GiB", ByteLimit::Limited(ByteSizeIec::gib(1)));
+}
+
+#[test]
+fn test_parse_default_unit() {
+    assert_eq!(
+        ByteSizeIec::parse_with_default_unit("512", Unit::MiB),
+        Ok(ByteSizeIec::mib(512))
+    );
+    assert_eq!(
+        ByteSizeIec::parse_with_default_unit("1.5", Unit::GiB),
+        Ok(ByteSizeIec::mib(1536))
+    );
+    assert_eq!(
+        ByteSizeIec::parse_with_default_unit("512KiB", Unit::MiB),
+        Ok(ByteSizeIec::kib(512))
+    );
+    assert_eq!(
+        ByteSizeSi::parse_with_default_unit("2.5 B", Unit::MB),
+        Ok(ByteSizeSi(2))
+    );
+    assert_eq!(
+        ByteSizeSi::parse_with_default_unit("2", Unit::KB),
+        Ok(ByteSizeSi::kb(2))
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_with_default_unit("1.5", Unit::B)),
+        Err(ErrorKind::FractionalBytes)
+    );
+    assert_eq!(
+        ByteSizeIec::parse_with_default_unit("15", Unit::B),
+        Ok(ByteSizeIec(15))
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_with_default_unit("2.", Unit::KB)),
+        Err(ErrorKind::BadNumber)
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_with_default_unit("2 ", Unit::KB)),
+        Err(ErrorKind::TrailingInput)
+    );
+}
+
+#[test]
+fn test_unit() {
+    assert_eq!(Unit::from_str("mib"), Ok(Unit::MiB));
+    assert_eq!(Unit::from_str("k"), Ok(Unit::KB));
+    assert_eq!(kind(Unit::from_str("kibi")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(Unit::GiB.bytes(), GIB);
+    assert_display!("kB", Unit::KB);
+}
+
+#[test]
+fn test_parse_strict() {
+    assert_eq!(ByteSizeSi::parse_strict("512kB"), Ok(ByteSizeSi::kb(512)));
+    assert_eq!(ByteSizeSi::parse_strict("512 MB"), Ok(ByteSizeSi::mb(512)));
+    assert_eq!(ByteSizeSi::parse_strict("512"), Ok(ByteSizeSi(512)));
+    assert_eq!(ByteSizeIec::parse_strict("4MiB"), Ok(ByteSizeIec::mib(4)));
+    assert_eq!(ByteSizeIec::parse_strict("4B"), Ok(ByteSizeIec(4)));
+
+    assert_eq!(
+        kind(ByteSizeSi::parse_strict("512Mb")),
+        Err(ErrorKind::AmbiguousUnit)
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_strict("512b")),
+        Err(ErrorKind::AmbiguousUnit)
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_strict("512mB")),
+        Err(ErrorKind::UnknownUnit)
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_strict("512M")),
+        Err(ErrorKind::UnknownUnit)
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_strict("512KB")),
+        Err(ErrorKind::UnknownUnit)
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_strict("512MiB")),
+        Err(ErrorKind::UnknownUnit)
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_strict("512MB")),
+        Err(ErrorKind::UnknownUnit)
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_strict("512Mi")),
+        Err(ErrorKind::UnknownUnit)
+    );
+    assert_eq!(
+        ByteSizeIec::parse_strict("0.50000000000000000000000KiB"),
+        Ok(ByteSizeIec(512))
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_strict("0.50000000000000000000001KiB")),
+        Err(ErrorKind::FractionalBytes)
+    );
+}
+
+#[test]
+fn test_parse_options() {
+    let options = ParseOptions::strict().bare_prefixes(true);
+    assert_eq!(
+        ByteSizeSi::parse_with("3M", &options),
+        Ok(ByteSizeSi::mb(3))
+    );
+    assert_eq!(
+        ByteSizeIec::parse_with("3Gi", &options),
+        Ok(ByteSizeIec::gib(3))
+    );
+
+    let options = ParseOptions::new().lowercase_b(false);
+    assert_eq!(
+        ByteSizeIec::parse_with("3MB", &options),
+        Ok(ByteSizeIec(3 * MB))
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_with("3mb", &options)),
+        Err(ErrorKind::AmbiguousUnit)
+    );
+
+    let options = ParseOptions::new().foreign_units(false);
+    assert_eq!(
+        ByteSizeIec::parse_with("3gib", &options),
+        Ok(ByteSizeIec::gib(3))
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_with("3gb", &options)),
+        Err(ErrorKind::UnknownUnit)
+    );
+
+    let options = ParseOptions::new().case_sensitive(true);
+    assert_eq!(
+        ByteSizeSi::parse_with("1.5MiB", &options),
+        Ok(ByteSizeSi::kb(1572) + ByteSizeSi(864))
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_with("1.5mib", &options)),
+        Err(ErrorKind::UnknownUnit)
+    );
+}
+
+#[test]
+fn test_error_detail() {
+    let e = ByteSizeIec::from_str("512 MiBs").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::UnknownUnit);
+    assert_eq!(e.span(), 4..8);
+    assert_eq!(e.suggestion(), Some("MiB"));
+    #[cfg(feature = "alloc")]
+    assert_display!(
+        "cannot recognize byte unit in string\n    512 MiBs\n        ^^^^ did you mean 'MiB'?",
+        e
+    );
+    #[cfg(not(feature = "alloc"))]
+    assert_display!(
+        "cannot recognize byte unit in string at 4..8 did you mean 'MiB'?",
+        e
+    );
+
+    let e = ByteSizeSi::from_str("2.5GB/s").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::TrailingInput);
+    assert_eq!(e.span(), 5..7);
+
+    let e = ByteSizeSi::from_str("1.5.3GB").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::BadNumber);
+    assert_eq!(e.span(), 0..4);
+
+    let e = ByteSizeSi::from_str("114.514 ").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::TrailingInput);
+    assert_eq!(e.span(), 7..8);
+
+    let e = ByteSizeSi::parse_strict("10Mb").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::AmbiguousUnit);
+    assert_eq!(e.suggestion(), Some("MB"));
+
+    let e = ByteSizeIec::parse_strict("10mib").unwrap_err();
+    assert_eq!(e.suggestion(), Some("MiB"));
+
+    let e = ByteSizeIec::parse_strict("10GB").unwrap_err();
+    assert_eq!(e.suggestion(), Some("GiB"));
+
+    let e = ByteSizeIec::from_str("10xyz").unwrap_err();
+    assert_eq!(e.suggestion(), None);
+
+    let e = ByteSizeSi::parse_strict("0.0001kB").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::FractionalBytes);
+
+    let e = ByteSizeSi::parse_strict("18.5EB").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::Overflow);
+
+    let e = RelativeByteSize::from_str("25%,min=1GiX").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::UnknownUnit);
+    assert_eq!(e.span(), 9..12);
+    #[cfg(feature = "alloc")]
+    assert_eq!(e.input(), "25%,min=1GiX");
+}
+
+#[test]
+fn test_parse_kubernetes() {
+    let parse = |s| ByteSizeIec::parse_dialect(s, Dialect::Kubernetes);
+
+    assert_eq!(parse("128Mi"), Ok(ByteSizeIec::mib(128)));
+    assert_eq!(parse("1G"), Ok(ByteSizeIec(GB)));
+    assert_eq!(parse("500M"), Ok(ByteSizeIec(500 * MB)));
+    assert_eq!(parse("1e9"), Ok(ByteSizeIec(GB)));
+    assert_eq!(parse("1E3"), Ok(ByteSizeIec(1000)));
+    assert_eq!(parse("1E"), Ok(ByteSizeIec(10u64.pow(18))));
+    assert_eq!(parse("12e-1"), Ok(ByteSizeIec(2)));
+    assert_eq!(parse("1500m"), Ok(ByteSizeIec(2)));
+    assert_eq!(parse("1000m"), Ok(ByteSizeIec(1)));
+    assert_eq!(parse("1m"), Ok(ByteSizeIec(1)));
+    assert_eq!(parse("1.5Gi"), Ok(ByteSizeIec::mib(1536)));
+    assert_eq!(parse(".5k"), Ok(ByteSizeIec(500)));
+    assert_eq!(parse("5."), Ok(ByteSizeIec(5)));
+    assert_eq!(parse("+5"), Ok(ByteSizeIec(5)));
+    assert_eq!(parse("-0"), Ok(ByteSizeIec(0)));
+    assert_eq!(parse("8Ei"), Ok(ByteSizeIec(8 * EIB)));
+
+    assert_eq!(kind(parse("")), Err(ErrorKind::Empty));
+    assert_eq!(kind(parse(".")), Err(ErrorKind::BadNumber));
+    assert_eq!(kind(parse("-1Gi")), Err(ErrorKind::Negative));
+    assert_eq!(kind(parse("1 Gi")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(kind(parse("1e")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(kind(parse("1K")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(kind(parse("16Ei")), Err(ErrorKind::Overflow));
+    assert_eq!(kind(parse("1e20")), Err(ErrorKind::Overflow));
+
+    let e = parse("128MiB").unwrap_err();
+    assert_eq!(e.span(), 3..6);
+    assert_eq!(e.suggestion(), Some("Mi"));
+}
+
+#[test]
+fn test_display_kubernetes() {
+    let iec = |n| ByteSizeIec(n).display_dialect(Dialect::Kubernetes);
+    let si = |n| ByteSizeSi(n).display_dialect(Dialect::Kubernetes);
+
+    assert_display!("0", iec(0));
+    assert_display!("512", iec(512));
+    assert_display!("1Ki", iec(KIB));
+    assert_display!("128Mi", iec(128 * MIB));
+    assert_display!("1536Mi", iec(1536 * MIB));
+    assert_display!("1500", iec(1500));
+    assert_display!("1500", si(1500));
+    assert_display!("2k", si(2000));
+    assert_display!("1G", si(GB));
+    assert_display!("134217728", si(128 * MIB));
+
+    let quantity = |s: &str| kubernetes::Quantity::from_str(s).unwrap().to_string();
+    assert_eq!(quantity("1e9"), "1e9");
+    assert_eq!(quantity("1000e3"), "1e6");
+    assert_eq!(quantity("1500e0"), "1500");
+    assert_eq!(quantity("1024Mi"), "1Gi");
+    assert_eq!(quantity("0.5Ki"), "512");
+    assert_eq!(quantity("1000k"), "1M");
+}
+
+#[test]
+fn test_parse_base1024() {
+    let parse = ByteSizeIec::parse_dialect;
+
+    assert_eq!(parse("512M", Dialect::Systemd), Ok(ByteSizeIec::mib(512)));
+    assert_eq!(parse("1.5G", Dialect::Systemd), Ok(ByteSizeIec::mib(1536)));
+    assert_eq!(parse("4096B", Dialect::Systemd), Ok(ByteSizeIec::kib(4)));
+    assert_eq!(parse("2E", Dialect::Systemd), Ok(ByteSizeIec::eib(2)));
+    assert_eq!(parse("10m", Dialect::Nginx), Ok(ByteSizeIec::mib(10)));
+    assert_eq!(parse("10K", Dialect::Nginx), Ok(ByteSizeIec::kib(10)));
+    assert_eq!(parse("2g", Dialect::Jvm), Ok(ByteSizeIec::gib(2)));
+    assert_eq!(parse("1t", Dialect::Jvm), Ok(ByteSizeIec::tib(1)));
+    assert_eq!(parse("1024", Dialect::Jvm), Ok(ByteSizeIec::kib(1)));
+
+    assert_eq!(
+        kind(parse("512m", Dialect::Systemd)),
+        Err(ErrorKind::UnknownUnit)
+    );
+    assert_eq!(
+        kind(parse("1t", Dialect::Nginx)),
+        Err(ErrorKind::UnknownUnit)
+    );
+    assert_eq!(kind(parse("1.5g", Dialect::Jvm)), Err(ErrorKind::BadNumber));
+    assert_eq!(
+        kind(parse("2gb", Dialect::Jvm)),
+        Err(ErrorKind::TrailingInput)
+    );
+    assert_eq!(kind(parse("-2g", Dialect::Jvm)), Err(ErrorKind::Negative));
+    assert_eq!(
+        kind(parse("16E", Dialect::Systemd)),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(kind(parse("", Dialect::Nginx)), Err(ErrorKind::Empty));
+}
+
+#[test]
+fn test_display_base1024() {
+    assert_display!("2g", ByteSizeIec::gib(2).display_dialect(Dialect::Jvm));
+    assert_display!(
+        "1536m",
+        ByteSizeIec::mib(1536).display_dialect(Dialect::Jvm)
+    );
+    assert_display!("1000", ByteSizeSi::kb(1).display_dialect(Dialect::Jvm));
+    assert_display!("0", ByteSizeIec(0).display_dialect(Dialect::Nginx));
+    assert_display!("1024g", ByteSizeIec::tib(1).display_dialect(Dialect::Nginx));
+    assert_display!(
+        "512M",
+        ByteSizeIec::mib(512).display_dialect(Dialect::Systemd)
+    );
+    assert_display!("3E", ByteSizeIec::eib(3).display_dialect(Dialect::Systemd));
+
+    for s in ["1536m", "2g", "7k", "12345"] {
+        let size = ByteSizeIec::parse_dialect(s, Dialect::Jvm).unwrap();
+        assert_eq!(s, size.display_dialect(Dialect::Jvm).to_string());
+    }
+}
+
+#[test]
+fn test_parse_coreutils() {
+    let parse = |s| ByteSizeIec::parse_dialect(s, Dialect::Coreutils);
+
+    assert_eq!(parse("1M"), Ok(ByteSizeIec::mib(1)));
+    assert_eq!(parse("10K"), Ok(ByteSizeIec::kib(10)));
+    assert_eq!(parse("10k"), Ok(ByteSizeIec::kib(10)));
+    assert_eq!(parse("10KB"), Ok(ByteSizeIec(10 * KB)));
+    assert_eq!(parse("10kB"), Ok(ByteSizeIec(10 * KB)));
+    assert_eq!(parse("10KiB"), Ok(ByteSizeIec::kib(10)));
+    assert_eq!(parse("2GB"), Ok(ByteSizeIec(2 * GB)));
+    assert_eq!(parse("3c"), Ok(ByteSizeIec(3)));
+    assert_eq!(parse("3w"), Ok(ByteSizeIec(6)));
+    assert_eq!(parse("3b"), Ok(ByteSizeIec(1536)));
+    assert_eq!(parse("2x512"), Ok(ByteSizeIec::kib(1)));
+    assert_eq!(parse("1Mx4x2"), Ok(ByteSizeIec::mib(8)));
+    assert_eq!(parse("0Z"), Ok(ByteSizeIec(0)));
+
+    assert_eq!(kind(parse("")), Err(ErrorKind::Empty));
+    assert_eq!(kind(parse("1.5M")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(kind(parse("1e")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(kind(parse("2x")), Err(ErrorKind::BadNumber));
+    assert_eq!(kind(parse("1Z")), Err(ErrorKind::Overflow));
+    assert_eq!(kind(parse("16Ex2")), Err(ErrorKind::Overflow));
+
+    // `head -c 1g` and `dd bs=1m` are invalid
+    for s in ["1m", "1g", "1t", "1mB", "1giB", "2x1m"] {
+        assert_eq!(kind(parse(s)), Err(ErrorKind::UnknownUnit), "{s}");
+    }
+    assert_eq!(parse("1kiB"), Ok(ByteSizeIec::kib(1)));
+    let e = Dialect::Coreutils.parse("1g").unwrap_err();
+    assert_eq!(e.suggestion(), Some("G"));
+}
+
+#[test]
+fn test_display_coreutils() {
+    let display = |n| ByteSizeIec(n).display_dialect(Dialect::Coreutils);
+
+    assert_display!("0", display(0));
+    assert_display!("512", display(512));
+    assert_display!("4M", display(4 * MIB));
+    assert_display!("1kB", display(KB));
+    assert_display!("1MB", display(MB));
+    assert_display!("1000K", display(1000 * KIB));
+    assert_display!("1536K", display(1536 * KIB));
+    assert_display!("1001", display(1001));
+}
+
+#[test]
+fn test_coreutils_operation() {
+    let apply = |op: &str, size| op.parse::<Operation>().map(|op| op.apply(size));
+
+    assert_eq!(apply("2G", 1), Ok(Some(2 * GIB)));
+    assert_eq!(apply("+2G", GIB), Ok(Some(3 * GIB)));
+    assert_eq!(apply("-1M", 3 * MIB), Ok(Some(2 * MIB)));
+    assert_eq!(apply("-1M", KIB), Ok(Some(0)));
+    assert_eq!(apply("<1M", 3 * MIB), Ok(Some(MIB)));
+    assert_eq!(apply(">1M", KIB), Ok(Some(MIB)));
+    assert_eq!(apply("/4K", 5000), Ok(Some(4096)));
+    assert_eq!(apply("%4K", 5000), Ok(Some(8192)));
+    assert_eq!(apply("+1", u64::MAX), Ok(None));
+    assert_eq!(Operation::RoundDown(0).apply(5), None);
+    assert_eq!(Operation::RoundUp(0).apply(5), None);
+
+    assert_eq!(kind(apply("%0", 1)), Err(ErrorKind::BadNumber));
+    assert_eq!(kind(apply("+", 1)), Err(ErrorKind::Empty));
+    let e = "+2Q".parse::<Operation>().unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::Overflow);
+    assert_eq!(e.span(), 1..3);
+}
+
+#[test]
+fn test_parse_redis() {
+    let parse = |s| ByteSizeIec::parse_dialect(s, Dialect::Redis);
+
+    assert_eq!(parse("100"), Ok(ByteSizeIec(100)));
+    assert_eq!(parse("100b"), Ok(ByteSizeIec(100)));
+    assert_eq!(parse("1k"), Ok(ByteSizeIec(KB)));
+    assert_eq!(parse("1kb"), Ok(ByteSizeIec::kib(1)));
+    assert_eq!(parse("1KB"), Ok(ByteSizeIec::kib(1)));
+    assert_eq!(parse("5m"), Ok(ByteSizeIec(5 * MB)));
+    assert_eq!(parse("5Mb"), Ok(ByteSizeIec::mib(5)));
+    assert_eq!(parse("2gb"), Ok(ByteSizeIec::gib(2)));
+
+    assert_eq!(kind(parse("")), Err(ErrorKind::Empty));
+    assert_eq!(kind(parse("-1")), Err(ErrorKind::Negative));
+    assert_eq!(kind(parse("1tb")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(kind(parse("1 gb")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(kind(parse("1.5gb")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(kind(parse("17179869184gb")), Err(ErrorKind::Overflow));
+}
+
+#[test]
+fn test_display_redis() {
+    let display = |n| ByteSizeIec(n).display_dialect(Dialect::Redis);
+
+    assert_display!("0", display(0));
+    assert_display!("1000", display(KB));
+    assert_display!("1kb", display(KIB));
+    assert_display!("1536mb", display(1536 * MIB));
+    assert_display!("2gb", display(2 * GIB));
+    assert_display!("1024gb", display(1024 * GIB));
+}
+
+#[test]
+fn test_parse_postgres() {
+    let parse = |s| ByteSizeIec::parse_dialect(s, Dialect::Postgres);
+
+    assert_eq!(parse("128MB"), Ok(ByteSizeIec::mib(128)));
+    assert_eq!(parse(" 64 kB "), Ok(ByteSizeIec::kib(64)));
+    assert_eq!(parse("1TB"), Ok(ByteSizeIec(1 << 40)));
+    assert_eq!(parse("100B"), Ok(ByteSizeIec(100)));
+    assert_eq!(parse("100"), Ok(ByteSizeIec(100)));
+    assert_eq!(parse("+1GB"), Ok(ByteSizeIec::gib(1)));
+    assert_eq!(parse("1.5GB"), Ok(ByteSizeIec::mib(1536)));
+    assert_eq!(parse("0.5B"), Ok(ByteSizeIec(0)));
+    assert_eq!(parse("1.5B"), Ok(ByteSizeIec(2)));
+    assert_eq!(parse("1e3"), Ok(ByteSizeIec(1000)));
+    assert_eq!(parse("0x10kB"), Ok(ByteSizeIec::kib(16)));
+    assert_eq!(parse("010kB"), Ok(ByteSizeIec::kib(8)));
+
+    assert_eq!(kind(parse("  ")), Err(ErrorKind::Empty));
+    assert_eq!(kind(parse("-1")), Err(ErrorKind::Negative));
+    assert_eq!(kind(parse("MB")), Err(ErrorKind::BadNumber));
+    assert_eq!(kind(parse("09")), Err(ErrorKind::BadNumber));
+    assert_eq!(kind(parse("0x")), Err(ErrorKind::BadNumber));
+    assert_eq!(kind(parse("16777216TB")), Err(ErrorKind::Overflow));
+    let e = parse("128mb").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::UnknownUnit);
+    assert_eq!(e.span(), 3..5);
+    assert_eq!(e.suggestion(), Some("MB"));
+    assert_eq!(kind(parse("1KB")), Err(ErrorKind::UnknownUnit));
+
+    let shared_buffers = crate::dialect::postgres::parse_with_base_unit("16384", 8 * KIB);
+    assert_eq!(shared_buffers, Ok(128 * MIB));
+}
+
+#[test]
+fn test_display_postgres() {
+    let display = |n| ByteSizeIec(n).display_dialect(Dialect::Postgres);
+
+    assert_display!("0", display(0));
+    assert_display!("1000B", display(1000));
+    assert_display!("8kB", display(8 * KIB));
+    assert_display!("1536MB", display(1536 * MIB));
+    assert_display!("1GB", display(GIB));
+    assert_display!("2048TB", display(2048 << 40));
+}
+
+#[test]
+fn test_parse_kernel() {
+    let parse = |s| ByteSizeIec::parse_dialect(s, Dialect::Kernel);
+
+    assert_eq!(parse("1234 kB"), Ok(ByteSizeIec::kib(1234)));
+    assert_eq!(parse("   16318480 kB\n"), Ok(ByteSizeIec::kib(16318480)));
+    assert_eq!(parse("1234kB"), Ok(ByteSizeIec::kib(1234)));
+    assert_eq!(parse("4096\n"), Ok(ByteSizeIec(4096)));
+
+    assert_eq!(kind(parse("\n")), Err(ErrorKind::Empty));
+    assert_eq!(kind(parse("max")), Err(ErrorKind::BadNumber));
+    assert_eq!(kind(parse("-1")), Err(ErrorKind::Negative));
+    assert_eq!(kind(parse("1 MB")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(
+        kind(parse("18014398509481984 kB")),
+        Err(ErrorKind::Overflow)
+    );
+    let e = parse("1234 KB").unwrap_err();
+    assert_eq!(e.span(), 5..7);
+    assert_eq!(e.suggestion(), Some("kB"));
+}
+
+#[test]
+fn test_display_kernel() {
+    let display = |n| ByteSizeIec(n).display_dialect(Dialect::Kernel);
+
+    assert_display!("0 kB", display(0));
+    assert_display!("1234 kB", display(1234 * KIB));
+    assert_display!("1000", display(KB));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_parse_kernel_file() {
+    use crate::dialect::kernel;
+
+    let meminfo = kernel::parse_file(include_str!("../tests/fixtures/meminfo")).unwrap();
+    assert_eq!(meminfo["MemTotal"], ByteSizeIec::kib(6158152));
+    assert_eq!(meminfo["Active(anon)"], ByteSizeIec::kib(12));
+    assert_eq!(meminfo["VmallocTotal"], ByteSizeIec::kib(34359738367));
+    assert_eq!(meminfo["Hugepagesize"], ByteSizeIec::mib(2));
+    assert!(!meminfo.contains_key("HugePages_Total"));
+    assert_eq!(meminfo.len(), 50);
+
+    let status = kernel::parse_file(include_str!("../tests/fixtures/status")).unwrap();
+    assert_eq!(status["VmRSS"], ByteSizeIec::kib(1244));
+    assert_eq!(status["VmPeak"], ByteSizeIec::kib(2640));
+    assert!(!status.contains_key("Name"));
+    assert!(!status.contains_key("Threads"));
+
+    let rss = kernel::entries(include_str!("../tests/fixtures/smaps"))
+        .filter_map(|entry| entry.ok().filter(|(key, _)| *key == "Rss"))
+        .fold(ByteSizeIec(0), |total, (_, size)| total + size);
+    assert_eq!(rss, ByteSizeIec::kib(116));
+
+    let e = kernel::parse_file("MemTotal: 1234 kB\nMemFree: 12x4 kB\n").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::UnknownUnit);
+    assert_eq!(e.input(), "MemFree: 12x4 kB");
+    assert_eq!(e.span(), 11..16);
+}
+
+/// Single-letter units of base 1024, where `M` isn't 1000000
+#[derive(Debug, Clone, Copy, Default)]
+struct Letter;
+
+impl UnitSystem for Letter {
+    fn base(&self) -> u64 {
+        KIB
+    }
+
+    fn symbols(&self) -> &'static [&'static str] {
+        &["K", "M", "G"]
+    }
+
+    fn unit_bytes(&self, symbol: &str) -> Option<u64> {
+        match symbol.to_ascii_uppercase().as_str() {
+            "K" => Some(KIB),
+            "M" => Some(MIB),
+            "G" => Some(GIB),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_unit_system() {
+    assert_eq!(ByteSizeSi(KB), ByteSize::with_system(KB, Si));
+    assert_eq!(ByteSizeIec::kib(1).in_system(Si), ByteSizeSi(KIB));
+    assert_eq!(ByteSizeSi::kb(1).in_system(Letter).to_string(), "1000B");
+
+    let letter = |s: &str| s.parse::<ByteSize<Letter>>().map(u64::from);
+    assert_eq!(letter("2k"), Ok(2 * KIB));
+    assert_eq!(letter("1.5 M"), Ok(1536 * KIB));
+    assert_eq!(letter("1MB"), Ok(MB));
+    assert_eq!(letter("1KiB"), Ok(KIB));
+    assert_eq!(ByteSize::<Letter>::b(1536 * KIB).to_string(), "1.5M");
+    assert_eq!(ByteSize::<Letter>::b(2048 * GIB).to_string(), "2048.0G");
+
+    let strict = |s| ByteSize::<Letter>::parse_strict(s).map(u64::from);
+    assert_eq!(strict("2K"), Ok(2 * KIB));
+    assert_eq!(strict("4B"), Ok(4));
+    assert_eq!(kind(strict("2k")), Err(ErrorKind::UnknownUnit));
+    assert_eq!(strict("2k").unwrap_err().suggestion(), Some("K"));
+    assert_eq!(kind(strict("2KiB")), Err(ErrorKind::UnknownUnit));
+
+    assert_eq!(format!("{:?}", ByteSizeSi(5)), "ByteSizeSi(5)");
+    assert_eq!(format!("{:?}", ByteSizeIec(5)), "ByteSizeIec(5)");
+    assert_eq!(format!("{:?}", ByteSizeJedec(5)), "ByteSizeJedec(5)");
+    assert_eq!(
+        format!("{:?}", ByteSize::new(5, System::Si)),
+        "ByteSize(5, Si)"
+    );
+    assert_eq!(
+        format!("{:?}", ByteSize::<Letter>::b(5u8)),
+        "ByteSize(5, Letter)"
+    );
+}
+
+#[test]
+fn test_jedec() {
+    let parse = |s: &str| s.parse::<ByteSizeJedec>();
+
+    assert_eq!(parse("512 KB"), Ok(ByteSizeJedec::kb(512)));
+    assert_eq!(parse("16GB"), Ok(ByteSizeJedec(16 * GIB)));
+    assert_eq!(parse("16gb"), Ok(ByteSizeJedec(16 * GIB)));
+    assert_eq!(parse("1.5M"), Ok(ByteSizeJedec(1536 * KIB)));
+    assert_eq!(parse("1KiB"), Ok(ByteSizeJedec::kb(1)));
+    assert_eq!(parse("100"), Ok(ByteSizeJedec(100)));
+    assert_eq!(
+        ByteSizeJedec::parse_strict("32MB"),
+        Ok(ByteSizeJedec::mb(32))
+    );
+    assert_eq!(
+        kind(ByteSizeJedec::parse_strict("32Mb")),
+        Err(ErrorKind::AmbiguousUnit)
+    );
+    assert_eq!(
+        kind(ByteSizeJedec::parse_strict("32MiB")),
+        Err(ErrorKind::UnknownUnit)
+    );
+    let e = ByteSizeJedec::parse_strict("5kB").unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::UnknownUnit);
+    assert_eq!(e.suggestion(), Some("KB"));
+
+    assert_display!("512B", ByteSizeJedec(512));
+    assert_display!("512.0KB", ByteSizeJedec::kb(512));
+    assert_display!("1.5MB", ByteSizeJedec(1536 * KIB));
+    assert_display!("16.0GB", ByteSizeJedec::gb(16));
+
+    let jedec = ByteSizeJedec::mb(1);
+    assert_eq!(ByteSizeIec::from(jedec), ByteSizeIec::mib(1));
+    assert_eq!(ByteSizeSi::from(jedec), ByteSizeSi(MIB));
+    assert_eq!(
+        ByteSizeJedec::from(ByteSizeIec::gib(2)),
+        ByteSizeJedec::gb(2)
+    );
+    assert_eq!(ByteSizeJedec::from(ByteSizeSi::kb(1)), ByteSizeJedec(KB));
+    assert_eq!(ByteSizeSi::mb(1).jedec().as_mb(), 0.95367431640625);
+    assert_eq!(jedec.iec().jedec(), jedec);
+}
+
+#[test]
+fn test_runtime_system() {
+    assert_eq!("si".parse(), Ok(System::Si));
+    assert_eq!("IEC".parse(), Ok(System::Iec));
+    assert_eq!("binary".parse(), Ok(System::Iec));
+    assert_eq!("Decimal".parse(), Ok(System::Si));
+    assert_eq!("jedec".parse(), Ok(System::Jedec));
+    assert_eq!(kind("".parse::<System>()), Err(ErrorKind::Empty));
+    assert_eq!(kind("metric".parse::<System>()), Err(ErrorKind::Invalid));
+    for system in System::ALL {
+        assert_eq!(system.to_string().parse(), Ok(system));
+    }
+
+    let size = ByteSize::new(1536 * KIB, System::Iec);
+    assert_display!("1.5MiB", size);
+    assert_display!("1.6MB", size.in_system(System::Si));
+    assert_display!("1.5MB", size.in_system(System::Jedec));
+
+    let size: ByteSize = "2KB".parse().unwrap();
+    assert_eq!(size, ByteSize::b(2 * KB));
+    assert_eq!(size.system(), System::Iec);
+    let size = ByteSize::<System>::parse_with("2KB", &ParseOptions::new().foreign_units(false));
+    assert_eq!(kind(size), Err(ErrorKind::UnknownUnit));
+
+    let size: ByteSize = ByteSizeSi::kb(1).into();
+    assert_eq!(size, ByteSize::new(KB, System::Si));
+    assert_eq!(size.system(), System::Si);
+    let size: ByteSize = ByteSizeJedec::kb(1).into();
+    assert_eq!(size.system(), System::Jedec);
+}
+
+#[test]
+fn test_parsed_byte_size() {
+    for s in ["1536MiB", "1536 MiB", "1.50  gib", "0010k", "42", "3 b"] {
+        let parsed = ParsedByteSize::parse(s).unwrap();
+        assert!(!parsed.is_changed());
+        assert_eq!(parsed.to_string(), s);
+    }
+
+    let mut parsed = ParsedByteSize::parse("1536  MiB").unwrap();
+    assert_eq!(parsed.bytes(), 1536 * MIB);
+    assert_eq!(parsed.number(), "1536");
+    assert_eq!(parsed.spacing(), "  ");
+    assert_eq!(parsed.symbol(), "MiB");
+    assert_eq!(parsed.unit(), Some(Unit::MiB));
+
+    parsed.set_bytes(2048 * MIB);
+    assert!(parsed.is_changed());
+    assert_display!("2048  MiB", parsed);
+    parsed.set_bytes(1536 * MIB + 512 * KIB);
+    assert_display!("1536.5  MiB", parsed);
+    parsed.set_bytes(MIB + KIB);
+    assert_display!("1049600  B", parsed);
+    parsed.set_bytes(1536 * MIB);
+    assert_display!("1536  MiB", parsed);
+
+    let mut parsed = ParsedByteSize::parse("1.5gb").unwrap();
+    parsed.set_bytes(1_250_000_000);
+    assert_display!("1.25gb", parsed);
+
+    let mut parsed = ParsedByteSize::parse("4096").unwrap();
+    assert_eq!(parsed.unit(), None);
+    parsed.set_bytes(8192);
+    assert_display!("8192", parsed);
+
+    assert_eq!(
+        kind(ParsedByteSize::parse("1.5 XB")),
+        Err(ErrorKind::UnknownUnit)
+    );
+}
+
+#[test]
+fn test_quantity() {
+    let cache = Quantity::<MiB>::new(512);
+    assert_eq!(cache.get(), 512);
+    assert_eq!(ByteSizeIec::try_from(cache), Ok(ByteSizeIec::mib(512)));
+    assert_eq!(ByteSizeSi::try_from(cache), Ok(ByteSizeSi(512 * MIB)));
+    assert_display!("512MiB", cache);
+    assert_eq!(format!("{cache:?}"), "Quantity(512MiB)");
+
+    assert_eq!(
+        Quantity::<GiB>::new(2).widen::<MiB>(),
+        Some(Quantity::new(2048))
+    );
+    assert_eq!(
+        Quantity::<GiB>::new(1).widen::<KiB>(),
+        Some(Quantity::new(MIB))
+    );
+    assert_eq!(Quantity::<GiB>::new(u64::MAX).widen::<MiB>(), None);
+    assert_eq!(
+        ByteSizeIec::try_from(Quantity::<EiB>::new(100)),
+        Err(ConversionError::new(ErrorKind::Overflow))
+    );
+    assert_eq!(
+        ByteSizeIec::try_from(Quantity::<EiB>::new(15)),
+        Ok(ByteSizeIec::eib(15))
+    );
+    assert_eq!(cache.convert::<GiB>(), None);
+    assert_eq!(
+        Quantity::<MiB>::new(2048).convert::<GiB>(),
+        Some(Quantity::new(2))
+    );
+    assert_eq!(Quantity::<MB>::new(1).convert::<KiB>(), None);
+    assert_eq!(Quantity::<MiB>::new(u64::MAX).convert::<KiB>(), None);
+    assert_eq!(Quantity::<MiB>::new(u64::MAX).checked_bytes(), None);
+
+    assert_eq!(Quantity::<MiB>::from_bytes(3 * MIB), Some(Quantity::new(3)));
+    assert_eq!(Quantity::<MiB>::from_bytes(3 * MIB + 1), None);
+
+    let mut total = cache + Quantity::new(256);
+    assert_eq!(total, Quantity::new(768));
+    total -= Quantity::new(512);
+    total *= 3u8;
+    assert_eq!(total, Quantity::new(768));
+    assert!(cache < total);
+    assert_eq!(cache * 2usize, Quantity::new(1024));
+    assert_eq!(cache / 4u32, Quantity::new(128));
+    total /= 3usize;
+    assert_eq!(total, Quantity::new(256));
+}
+
+#[test]
+fn test_display_unit_boundary() {
+    for exp in 1..=6 {
+        assert_eq!(ByteSizeSi(KB.pow(exp)).to_string().get(..3), Some("1.0"));
+        assert_eq!(ByteSizeIec(KIB.pow(exp)).to_string().get(..3), Some("1.0"));
+    }
+    assert_display!("999B", ByteSizeSi(999));
+    assert_display!("1000.0kB", ByteSizeSi(MB - 1));
+    assert_display!("18.4EB", ByteSizeSi::MAX);
+    assert_display!("16.0EiB", ByteSizeIec::MAX);
+}
+
+/// Display of the previous floating point path
+fn display_f64<S: UnitSystem>(size: ByteSize<S>) -> String {
+    let (base, symbols) = (size.1.base(), size.1.symbols());
+    if size.0 < base {
+        return format!("{}B", size.0);
+    }
+
+    let (mut unit, mut exp) = (base, 1);
+    while exp < symbols.len()
+        && let Some(next) = unit.checked_mul(base)
+        && next <= size.0
+    {
+        unit = next;
+        exp += 1;
+    }
+    format!("{:.1}{}", size.0 as f64 / unit as f64, symbols[exp - 1])
+}
+
+#[test]
+fn test_format_to() {
+    let mut buf = [0; 16];
+    assert_eq!(ByteSizeIec(1023).format_to(&mut buf), Ok("1023B"));
+    assert_eq!(ByteSizeIec(MIB - 1).format_to(&mut buf), Ok("1024.0KiB"));
+    assert_eq!(ByteSizeSi::MAX.format_to(&mut buf), Ok("18.4EB"));
+    assert_eq!(ByteSizeJedec::gb(3).format_to(&mut buf), Ok("3.0GB"));
+    assert_eq!(
+        ByteSizeIec::mib(1536).format_to(&mut [0; 5]),
+        Err(core::fmt::Error)
+    );
+
+    // Ties of tenths, which round half to even
+    assert_eq!(ByteSizeIec(1280).format_to(&mut buf), Ok("1.2KiB"));
+    assert_eq!(ByteSizeIec(1792).format_to(&mut buf), Ok("1.8KiB"));
+    assert_eq!(ByteSizeSi(1250).format_to(&mut buf), Ok("1.2kB"));
+    assert_eq!(ByteSizeSi(1050).format_to(&mut buf), Ok("1.1kB"));
+    assert_eq!(ByteSizeSi(1150).format_to(&mut buf), Ok("1.1kB"));
+}
+
+#[test]
+fn test_format_to_matches_f64() {
+    let mut values = vec![0, 1, u64::MAX, u64::MAX - 1, 1 << 53, (1 << 53) + 1];
+    for base in [KB, KIB] {
+        for exp in 1..=6 {
+            let unit = base.pow(exp);
+            for k in [1, 2, 10, 999, 1000, 1023, 1024] {
+                let Some(n) = unit.checked_mul(k) else {
+                    continue;
+                };
+                for delta in [0, 1, unit / 20, unit / 20 + 1, unit / 2] {
+                    values.push(n.saturating_add(delta));
+                    values.push(n.saturating_sub(delta + 1));
+                }
+            }
+        }
+    }
+
+    // xorshift over every magnitude
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    for _ in 0..100_000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        values.push(state >> (state % 64));
+    }
+
+    let mut buf = [0; 16];
+    for n in values {
+        for system in System::ALL {
+            let size = ByteSize::new(n, system);
+            let expected = display_f64(size);
+            assert_eq!(size.format_to(&mut buf), Ok(expected.as_str()), "{n}");
+            assert_eq!(size.to_string(), expected, "{n}");
+        }
+    }
+}
+
+#[test]
+fn test_parse_const() {
+    const MAX_BODY: ByteSizeIec = ByteSizeIec::parse_const("16MiB");
+    assert_eq!(MAX_BODY, ByteSizeIec::mib(16));
+    assert_eq!(bytesize!("1.5 GiB"), ByteSizeIec::mib(1536));
+    assert_eq!(bytesize!(ByteSizeSi, "2.5kb"), ByteSizeSi(2500));
+    assert_eq!(bytesize!(ByteSizeJedec, "4K"), ByteSizeJedec::kb(4));
+    assert_eq!(bytesize!(ByteSizeJedec, "4kB"), ByteSizeJedec::kb(4));
+    assert_eq!(bytesize!(ByteSizeSi, "4KiB"), ByteSizeSi(4096));
+
+    assert_eq!(
+        parse_byte_size_const("20EiB", System::Iec),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(
+        parse_byte_size_const("20.5EiB", System::Iec),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(
+        parse_byte_size_const("18446744073709551616", System::Iec),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(
+        parse_byte_size_const("0.12345678901234567890123MiB", System::Iec),
+        Ok(129453)
+    );
+    assert_eq!(
+        ByteSizeIec::parse_const("3.14159265358979323846EiB"),
+        ByteSizeIec(3622009729038561421)
+    );
+}
+
+#[test]
+fn test_parse_const_matches_runtime() {
+    let inputs = [
+        "",
+        "0",
+        "1",
+        "1023",
+        "18446744073709551615",
+        "1.5",
+        "1.5 MiB",
+        "1.1kB",
+        "4.35KB",
+        "0.1 GB",
+        "0.29kb",
+        "1.25 k",
+        "12 ",
+        "12x",
+        "12.5.3",
+        "1.5.3GB",
+        "-1KiB",
+        "-x",
+        ".5MB",
+        "1.MB",
+        "1 MiBs",
+        "1MiB!",
+        "8 EiB",
+        "15.99EiB",
+        "3GiB",
+        "2 Gi",
+        "7 T",
+        "7 t",
+        "5 EB",
+        "123456789.123456 MB",
+        "3.14159265358979323846EiB",
+        "0.12345678901234567890123MiB",
+        "18446744073709551615.5",
+        "1 kB ",
+        "K",
+        "1k",
+        "1 KB",
+        "1 mb",
+        "1 b",
+        "1 bit",
+    ];
+
+    for input in inputs {
+        for system in System::ALL {
+            let runtime = match system {
+                System::Si => kind(ByteSizeSi::from_str(input).map(u64::from)),
+                System::Iec => kind(ByteSizeIec::from_str(input).map(u64::from)),
+                System::Jedec => kind(ByteSizeJedec::from_str(input).map(u64::from)),
+            };
+            assert_eq!(
+                parse_byte_size_const(input, system),
+                runtime,
+                "{input:?} in {system}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_ext() {
+    assert_eq!(64.mib(), ByteSizeIec::mib(64));
+    assert_eq!(512u16.kib(), ByteSizeIec::kib(512));
+    assert_eq!(2usize.gb(), ByteSizeSi::gb(2));
+    assert_eq!(3i64.eb(), ByteSizeSi::eb(3));
+    assert_eq!(1.5.gib(), ByteSizeIec::mib(1536));
+    assert_eq!(0.5f32.kb(), ByteSizeSi(500));
+
+    // Rounded to the nearest byte
+    assert_eq!(0.0005.kb(), ByteSizeSi(1));
+    assert_eq!(0.0004.kb(), ByteSizeSi(0));
+    assert_eq!(1.1.kib(), ByteSizeIec(1126));
+
+    assert_eq!(16.checked_eib(), None);
+    assert_eq!(15.checked_eib(), Some(ByteSizeIec::eib(15)));
+    assert_eq!((-1).checked_mb(), None);
+    assert_eq!(u128::MAX.checked_kb(), None);
+    assert_eq!((-0.5).checked_kib(), None);
+    assert_eq!(f64::NAN.checked_kib(), None);
+    assert_eq!(f64::INFINITY.checked_kib(), None);
+    assert_eq!(16.0.checked_eib(), None);
+}
+
+#[test]
+#[should_panic]
+fn test_ext_overflow() {
+    let _ = 16.eib();
+}
+
+#[test]
+fn test_conversion() {
+    assert_eq!(ByteSizeIec::from(1u8), ByteSizeIec(1));
+    assert_eq!(ByteSizeSi::from(1024u16), ByteSizeSi(1024));
+    assert_eq!(ByteSizeIec::from(u32::MAX), ByteSizeIec(u32::MAX as u64));
+
+    let buf = [0u8; 4096];
+    assert_eq!(ByteSizeIec::try_from(buf.len()), Ok(ByteSizeIec::kib(4)));
+    assert_eq!(ByteSizeSi::try_from(1000i64), Ok(ByteSizeSi::kb(1)));
+    assert_eq!(ByteSizeSi::try_from(7i32), Ok(ByteSizeSi(7)));
+    assert_eq!(
+        ByteSizeIec::try_from(u64::MAX as u128),
+        Ok(ByteSizeIec::MAX)
+    );
+    assert_eq!(ByteSizeIec::try_from(1536.0), Ok(ByteSizeIec(1536)));
+
+    let error = |result: Result<ByteSizeIec, ConversionError>| result.map_err(|e| e.kind());
+    assert_eq!(
+        error(ByteSizeIec::try_from(-1i32)),
+        Err(ErrorKind::Negative)
+    );
+    assert_eq!(
+        error(ByteSizeIec::try_from(i64::MIN)),
+        Err(ErrorKind::Negative)
+    );
+    assert_eq!(
+        error(ByteSizeIec::try_from(-1i128)),
+        Err(ErrorKind::Negative)
+    );
+    assert_eq!(
+        error(ByteSizeIec::try_from(1u128 << 64)),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(error(ByteSizeIec::try_from(-0.5)), Err(ErrorKind::Negative));
+    assert_eq!(
+        error(ByteSizeIec::try_from(1.5)),
+        Err(ErrorKind::FractionalBytes)
+    );
+    assert_eq!(
+        error(ByteSizeIec::try_from(f64::NAN)),
+        Err(ErrorKind::BadNumber)
+    );
+    assert_eq!(
+        error(ByteSizeIec::try_from(f64::INFINITY)),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(
+        error(ByteSizeIec::try_from(u64::MAX as f64)),
+        Err(ErrorKind::Overflow)
+    );
+
+    assert_eq!(usize::try_from(ByteSizeIec::kib(4)), Ok(4096));
+    assert_eq!(u32::try_from(ByteSizeSi::gb(4)), Ok(4_000_000_000));
+    assert_eq!(
+        u32::try_from(ByteSizeIec::gib(4)).map_err(|e| e.kind()),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(i64::try_from(ByteSizeIec::eib(7)), Ok(7 << 60));
+    assert_eq!(
+        i64::try_from(ByteSizeIec::MAX).map_err(|e| e.kind()),
+        Err(ErrorKind::Overflow)
+    );
+    assert_eq!(
+        ConversionError::new(ErrorKind::Overflow).to_string(),
+        "bytesize is too large to represent"
+    );
+}
+
+#[test]
+fn test_scalar_arithmetic() {
+    let buf = [0u8; 512];
+    assert_eq!(ByteSizeIec(512) * buf.len(), ByteSizeIec::kib(256));
+    assert_eq!(ByteSizeSi::mb(3) / 3usize, ByteSizeSi::mb(1));
+    assert_eq!(ByteSizeIec::kib(1) / 3u32, ByteSizeIec(341));
+
+    let mut size = ByteSizeSi::kb(2);
+    size *= 3usize;
+    size /= 2u8;
+    assert_eq!(size, ByteSizeSi::kb(3));
+}
+
+#[test]
+fn test_parse_bytes() {
+    assert_eq!(
+        ByteSizeIec::parse_bytes(b"16 MiB"),
+        Ok(ByteSizeIec::mib(16))
+    );
+    assert_eq!(
+        kind(ByteSizeSi::parse_bytes(b"16 MiBs")),
+        Err(ErrorKind::UnknownUnit)
+    );
+
+    let error = ByteSizeIec::parse_bytes(b"16 \xffiB").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Invalid);
+    assert_eq!(error.span(), 3..3);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_parse_os_str() {
+    use std::ffi::OsStr;
+
+    assert_eq!(
+        ByteSizeIec::parse_os_str(OsStr::new("1.5GiB")),
+        Ok(ByteSizeIec::mib(1536))
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_os_str(OsStr::new("1.5"))),
+        Err(ErrorKind::FractionalBytes)
+    );
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let error = ByteSizeIec::parse_os_str(OsStr::from_bytes(b"1\x80")).unwrap_err();
+        assert_eq!((error.kind(), error.span()), (ErrorKind::Invalid, 1..1));
+    }
+}
+
+#[test]
+fn test_parse_prefix() {
+    assert_eq!(
+        ByteSizeIec::parse_prefix("1.5GiB, 2GiB"),
+        Ok((ByteSizeIec::mib(1536), ", 2GiB"))
+    );
+    assert_eq!(
+        ByteSizeSi::parse_prefix("512 kB/s"),
+        Ok((ByteSizeSi::kb(512), "/s"))
+    );
+    assert_eq!(ByteSizeIec::parse_prefix("64"), Ok((ByteSizeIec(64), "")));
+    assert_eq!(ByteSizeIec::parse_prefix("64 "), Ok((ByteSizeIec(64), " ")));
+    assert_eq!(
+        ByteSizeIec::parse_prefix("512 bytes"),
+        Ok((ByteSizeIec(512), " bytes"))
+    );
+    assert_eq!(ByteSizeIec::parse_prefix("7.x"), Ok((ByteSizeIec(7), ".x")));
+    assert_eq!(
+        ByteSizeIec::parse_prefix("2 GiBs"),
+        Ok((ByteSizeIec(2), " GiBs"))
+    );
+
+    assert_eq!(kind(ByteSizeIec::parse_prefix("")), Err(ErrorKind::Empty));
+    assert_eq!(
+        kind(ByteSizeIec::parse_prefix("x1")),
+        Err(ErrorKind::BadNumber)
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_prefix("-1")),
+        Err(ErrorKind::Negative)
+    );
+    assert_eq!(
+        kind(ByteSizeIec::parse_prefix("1.5 apples")),
+        Err(ErrorKind::FractionalBytes)
+    );
+
+    let error = ByteSizeIec::parse_prefix("size 99EiB!").map(|_| ());
+    assert_eq!(kind(error), Err(ErrorKind::BadNumber));
+    let error = ByteSizeIec::parse_prefix("99EiB!").unwrap_err();
+    assert_eq!((error.kind(), error.span()), (ErrorKind::Overflow, 0..5));
+}
+
+#[test]
+fn test_find_sizes() {
+    let find = |text| scan::find_sizes(text).collect::<Vec<_>>();
+
+    assert_eq!(
+        find("uploaded 1.2 GiB in 3s (rss 512MB)"),
+        [
+            (9..16, ByteSizeIec(1288490188)),
+            (28..33, ByteSizeIec::b(512 * MB))
+        ]
+    );
+    assert_eq!(
+        find("1kb,2KiB;3 mib/s 10-20MB"),
+        [
+            (0..3, ByteSizeIec(1000)),
+            (4..8, ByteSizeIec::kib(2)),
+            (9..14, ByteSizeIec::mib(3)),
+            (20..24, ByteSizeIec::b(20 * MB)),
+        ]
+    );
+    assert_eq!(find("größe: 4 GiB!"), [(9..14, ByteSizeIec::gib(4))]);
+
+    // False positives
+    for text in [
+        "x86MB", "5MBps", "1.2.3GB", "1,024 MB", "-5MB", "max_8GB", "3 k", "16k", "12 files",
+        "99EiB", "GiB", "4 GiBé", "5.GB",
+    ] {
+        assert_eq!(find(text), [], "{text}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_replace_sizes() {
+    assert_eq!(
+        scan::replace_sizes("rss 1536MiB, cache 2048 KiB", |size| size.si()),
+        "rss 1.6GB, cache 2.1MB"
+    );
+    assert_eq!(
+        scan::replace_sizes("total: 3 GB (x86MB)", |size| size.0),
+        "total: 3000000000 (x86MB)"
+    );
+    assert_eq!(scan::replace_sizes("", |size| size), "");
+}
+
+/// Error type of parser combinators recording the bytesize error kind
+#[cfg(any(feature = "nom", feature = "winnow"))]
+#[derive(Debug, PartialEq)]
+struct KindError(Option<ErrorKind>);
+
+#[cfg(feature = "nom")]
+impl<I> nom::error::ParseError<I> for KindError {
+    fn from_error_kind(_: I, _: nom::error::ErrorKind) -> Self {
+        Self(None)
+    }
+
+    fn append(_: I, _: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+#[cfg(feature = "nom")]
+impl<I> nom::error::FromExternalError<I, Error> for KindError {
+    fn from_external_error(_: I, _: nom::error::ErrorKind, e: Error) -> Self {
+        Self(Some(e.kind()))
+    }
+}
+
+#[cfg(feature = "winnow")]
+impl<I: winnow::stream::Stream> winnow::error::ParserError<I> for KindError {
+    type Inner = Self;
+
+    fn from_input(_: &I) -> Self {
+        Self(None)
+    }
+
+    fn into_inner(self) -> Result<Self::Inner, Self> {
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "winnow")]
+impl<I> winnow::error::FromExternalError<I, Error> for KindError {
+    fn from_external_error(_: &I, e: Error) -> Self {
+        Self(Some(e.kind()))
+    }
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn test_nom() {
+    use nom::{IResult, Parser, bytes::complete::tag, character::complete::space1};
+
+    use crate::nom::{byte_size, number, unit};
+
+    fn rule(input: &str) -> IResult<&str, ByteSizeSi, KindError> {
+        let (input, (_, _, size)) = (tag("if size >"), space1, byte_size).parse(input)?;
+        Ok((input, size))
+    }
+
+    let kind = |kind| nom::Err::Error(KindError(Some(kind)));
+
+    assert_eq!(
+        rule("if size > 10MiB then"),
+        Ok((" then", ByteSizeSi::b(10 * MIB)))
+    );
+    assert_eq!(rule("if size > 1.5 GB"), Ok(("", ByteSizeSi::mb(1500))));
+    assert_eq!(rule("if size > 10 then"), Ok((" then", ByteSizeSi(10))));
+    assert_eq!(rule("if size > 99EiB"), Err(kind(ErrorKind::Overflow)));
+    assert_eq!(rule("if size > -1"), Err(kind(ErrorKind::Negative)));
+    assert_eq!(
+        rule("if size > 1.5 apples"),
+        Err(kind(ErrorKind::FractionalBytes))
+    );
+
+    assert_eq!(number::<KindError>("1.5GiB"), Ok(("GiB", "1.5")));
+    assert_eq!(number::<KindError>("1.GiB"), Ok((".GiB", "1")));
+    assert_eq!(number::<KindError>("x"), Err(kind(ErrorKind::BadNumber)));
+    assert_eq!(unit::<KindError>("gib)"), Ok((")", Unit::GiB)));
+    assert_eq!(unit::<KindError>("GiBs"), Err(kind(ErrorKind::UnknownUnit)));
+    assert_eq!(unit::<KindError>(""), Err(kind(ErrorKind::UnknownUnit)));
+}
+
+#[cfg(feature = "winnow")]
+#[test]
+fn test_winnow() {
+    use winnow::{ModalResult, Parser, ascii::space1, error::ErrMode};
+
+    use crate::winnow::{byte_size, number, unit};
+
+    fn rule(input: &mut &str) -> ModalResult<ByteSizeSi, KindError> {
+        ("if size >", space1, byte_size)
+            .map(|(_, _, size)| size)
+            .parse_next(input)
+    }
+
+    let kind = |kind| ErrMode::Backtrack(KindError(Some(kind)));
+
+    let mut input = "if size > 10MiB then";
+    assert_eq!(rule(&mut input), Ok(ByteSizeSi::b(10 * MIB)));
+    assert_eq!(input, " then");
+    assert_eq!(rule(&mut "if size > 10 then"), Ok(ByteSizeSi(10)));
+    assert_eq!(rule(&mut "if size > 99EiB"), Err(kind(ErrorKind::Overflow)));
+    assert_eq!(rule(&mut "if size > -1"), Err(kind(ErrorKind::Negative)));
+
+    let mut input = "1.5GiB";
+    assert_eq!(number::<KindError>(&mut input), Ok("1.5"));
+    assert_eq!(unit::<KindError>(&mut input), Ok(Unit::GiB));
+    assert_eq!(input, "");
+
+    let mut input = "GiBs";
+    assert_eq!(
+        unit::<KindError>(&mut input),
+        Err(KindError(Some(ErrorKind::UnknownUnit)))
+    );
+    assert_eq!(input, "GiBs");
+    assert_eq!(
+        number::<KindError>(&mut "x"),
+        Err(KindError(Some(ErrorKind::BadNumber)))
+    );
+}
+
+#[test]
+fn test_cmp_human() {
+    // Ordered by `LC_ALL=C sort -h`
+    let sorted = [
+        "-2K", "-1K", "-0.5K", "-1", "-", "0", "0Z", "abc", "1 M", "100", "1023", "1K", "1kB",
+        "512K", "999K", "2000K", "1.0M", "1M", "1.9M", "2M", "3.2MiB", "1536M", "1.5G", "  4G",
+        "4.0GB", "20G", "7T", "10E", "99EiB", "1Z", "2YiB",
+    ];
+    for (i, a) in sorted.iter().enumerate() {
+        for (j, b) in sorted.iter().enumerate() {
+            assert_eq!(sort::cmp_human(a, b), i.cmp(&j), "{a} {b}");
+        }
+    }
+
+    assert_eq!(sort::cmp_human("2000K", "1M"), Ordering::Less);
+    assert_eq!(sort::cmp_human("1536M", "1.5G"), Ordering::Less);
+    assert_eq!(sort::cmp_human("1kB", "1KiB"), Ordering::Greater);
+
+    let key = sort::sort_key("1.5G /usr");
+    assert_eq!(key.order(), 3);
+    assert_eq!(key.line(), "1.5G /usr");
+    assert_eq!(sort::sort_key("1kB").order(), 1);
+    assert_eq!(sort::sort_key("1mB").order(), 0);
+    assert_eq!(sort::sort_key("-2G").order(), -3);
+    assert_eq!(sort::sort_key("0Z").order(), 0);
+    assert_eq!(sort::sort_key("").order(), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_sort_by_size() {
+    let mut usage = [
+        ("/usr", "1.5G"),
+        ("/etc", "12M"),
+        ("/home", "20G"),
+        ("/tmp", "12M"),
+    ];
+    sort::sort_by_size(&mut usage, |(_, size)| size);
+    assert_eq!(usage.map(|(dir, _)| dir), ["/etc", "/tmp", "/usr", "/home"]);
+}
+
+#[test]
+fn test_approx() {
+    macro_rules! assert_approx {
+        ($s:literal, $v:expr) => {
+            assert_eq!($s, &$v.approx().to_string());
+        };
+    }
+
+    assert_approx!("less than 1 kB", ByteSizeSi(0));
+    assert_approx!("less than 1 KiB", ByteSizeIec(1023));
+    assert_approx!("1 KiB", ByteSizeIec(1024));
+    assert_approx!("1.5 MiB", ByteSizeIec::kib(1536));
+    assert_approx!("about 1.5 MiB", ByteSizeIec::kib(1500));
+    assert_approx!("about 2 MB", ByteSizeSi::kb(1950));
+    assert_approx!("over 1 MB", ByteSizeSi::kb(1100));
+    assert_approx!("over 1.5 MB", ByteSizeSi::kb(1700));
+    assert_approx!("about 1 MB", ByteSizeSi::kb(1040));
+    assert_approx!("over 1 TB", ByteSizeSi::gb(1200));
+    assert_approx!("512 MiB", ByteSizeIec::mib(512));
+    assert_approx!("about 512 MiB", ByteSizeIec::kib(512 * 1024 + 100));
+    assert_approx!("about 13 GB", ByteSizeSi::mb(12700));
+    assert_approx!("about 1 MB", ByteSizeSi(999_999));
+    assert_approx!("1000 MiB", ByteSizeIec::mib(1000));
+    assert_approx!("about 1 GiB", ByteSizeIec::kib(1024 * 1024 - 1));
+    assert_approx!("about 18 EB", ByteSizeSi::MAX);
+    assert_approx!("about 16 EiB", ByteSizeIec::MAX);
+    assert_approx!("1020 MB", ByteSizeJedec::mb(1020));
+    assert_approx!("about 1 GB", ByteSizeJedec::kb(1024 * 1024 - 100));
+
+    let french = Phrases::new()
+        .less_than("moins de {}")
+        .about("environ {}")
+        .over("plus de {}")
+        .exact("{} pile")
+        .decimal_separator(",");
+    let approx = |size: ByteSizeSi| size.approx_with(french).to_string();
+    assert_eq!(approx(ByteSizeSi(10)), "moins de 1 kB");
+    assert_eq!(approx(ByteSizeSi::kb(1480)), "environ 1,5 MB");
+    assert_eq!(approx(ByteSizeSi::gb(1200)), "plus de 1 TB");
+    assert_eq!(approx(ByteSizeSi::kb(2500)), "2,5 MB pile");
+    assert_eq!(
+        ByteSizeSi(1)
+            .approx_with(Phrases::new().less_than("tiny"))
+            .to_string(),
+        "tiny"
+    );
 }
@@ -0,0 +1,67 @@
+//! [winnow](https://crates.io/crates/winnow) parsers of bytesize
+//!
+//! They accept the same grammar as [`FromStr`](core::str::FromStr), and fail with
+//! [`Error`] as the external error, so the [`ErrorKind`](crate::ErrorKind) is kept
+//! by error types which record it. The input is left untouched on failure.
+//!
+//! ```
+//! use winnow::{ModalResult, Parser, ascii::space1};
+//! use typed_bytesize::{ByteSizeIec, winnow::byte_size};
+//!
+//! fn rule(input: &mut &str) -> ModalResult<ByteSizeIec> {
+//!     ("if size >", space1, byte_size).map(|(_, _, size)| size).parse_next(input)
+//! }
+//!
+//! let mut input = "if size > 10MiB then";
+//! assert_eq!(rule(&mut input), Ok(ByteSizeIec::mib(10)));
+//! assert_eq!(input, " then");
+//! ```
+
+use winnow::error::{FromExternalError, ParserError};
+
+use crate::{ByteSize, Error, ParseOptions, Unit, UnitSystem};
+
+/// Parse a number like `1` or `1.5`.
+pub fn number<'a, E>(input: &mut &'a str) -> Result<&'a str, E>
+where
+    E: ParserError<&'a str> + FromExternalError<&'a str, Error>,
+{
+    let len = crate::parse_number_prefix(input).map_err(|e| failure(input, e))?;
+    let (number, rest) = input.split_at(len);
+    *input = rest;
+    Ok(number)
+}
+
+/// Parse a unit symbol like `MiB` or `k`, in any case.
+pub fn unit<'a, E>(input: &mut &'a str) -> Result<Unit, E>
+where
+    E: ParserError<&'a str> + FromExternalError<&'a str, Error>,
+{
+    let (unit, len) = crate::parse_unit_prefix(input).map_err(|e| failure(input, e))?;
+    *input = &input[len..];
+    Ok(unit)
+}
+
+/// Parse bytesize like `1.5 GiB`, whose unit is the whole word following the number.
+///
+/// The size is in bytes if the word isn't a unit, as [`ByteSize::parse_prefix`] does.
+pub fn byte_size<'a, S, E>(input: &mut &'a str) -> Result<ByteSize<S>, E>
+where
+    S: UnitSystem + Default,
+    E: ParserError<&'a str> + FromExternalError<&'a str, Error>,
+{
+    let system = S::default();
+    let (n, len) = crate::parse_prefix_with(input, &ParseOptions::new(), &system)
+        .map_err(|e| failure(input, e))?;
+    *input = &input[len..];
+    Ok(ByteSize(n, system))
+}
+
+/// Backtracking error at the offending part of `input`
+fn failure<'a, E>(input: &&'a str, error: Error) -> E
+where
+    E: FromExternalError<&'a str, Error>,
+{
+    let part = &input[error.span().start..];
+    E::from_external_error(&part, error)
+}